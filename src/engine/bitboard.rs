@@ -0,0 +1,149 @@
+use super::structs::enums::{Color, Position};
+
+// Set of squares, bit 0 is a1, bit 7 is h1 and bit 63 is h8
+pub type Bitboard = u64;
+
+// Rank and file offsets, rays with even index go towards higher squares
+const DIRECTIONS: [(i8, i8); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, -1), (1, -1), (-1, 1)];
+const ROOK_DIRECTIONS: [usize; 4] = [0, 1, 2, 3];
+const BISHOP_DIRECTIONS: [usize; 4] = [4, 5, 6, 7];
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [(2, 1), (2, -1), (-2, 1), (-2, -1), (1, 2), (1, -2), (-1, 2), (-1, -2)];
+const KING_OFFSETS: [(i8, i8); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, -1), (1, -1), (-1, 1)];
+
+const fn offset_table(offsets: &[(i8, i8)]) -> [Bitboard; 64] {
+    let mut table = [0; 64];
+    let mut square = 0;
+    while square < 64 {
+        let rank = (square / 8) as i8;
+        let file = (square % 8) as i8;
+        let mut i = 0;
+        while i < offsets.len() {
+            let next_rank = rank + offsets[i].0;
+            let next_file = file + offsets[i].1;
+            if next_rank >= 0 && next_rank < 8 && next_file >= 0 && next_file < 8 {
+                table[square] |= 1 << (next_rank * 8 + next_file);
+            }
+            i += 1;
+        }
+        square += 1;
+    }
+    table
+}
+
+const fn pawn_table(rank_increment: i8) -> [Bitboard; 64] {
+    offset_table(&[(rank_increment, 1), (rank_increment, -1)])
+}
+
+const fn ray_table() -> [[Bitboard; 64]; 8] {
+    let mut table = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let mut square = 0;
+        while square < 64 {
+            let mut rank = (square / 8) as i8 + DIRECTIONS[direction].0;
+            let mut file = (square % 8) as i8 + DIRECTIONS[direction].1;
+            while rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                table[direction][square] |= 1 << (rank * 8 + file);
+                rank += DIRECTIONS[direction].0;
+                file += DIRECTIONS[direction].1;
+            }
+            square += 1;
+        }
+        direction += 1;
+    }
+    table
+}
+
+static KNIGHT_ATTACKS: [Bitboard; 64] = offset_table(&KNIGHT_OFFSETS);
+static KING_ATTACKS: [Bitboard; 64] = offset_table(&KING_OFFSETS);
+static WHITE_PAWN_ATTACKS: [Bitboard; 64] = pawn_table(1);
+static BLACK_PAWN_ATTACKS: [Bitboard; 64] = pawn_table(-1);
+static RAYS: [[Bitboard; 64]; 8] = ray_table();
+
+pub fn square_of(position: Position) -> usize {
+    ((7 - position.0) * 8 + position.1) as usize
+}
+
+pub fn position_of(square: usize) -> Position {
+    (7 - (square / 8) as i8, (square % 8) as i8)
+}
+
+pub fn bit(position: Position) -> Bitboard {
+    1 << square_of(position)
+}
+
+pub struct Squares(Bitboard);
+
+impl Iterator for Squares {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let square = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+}
+
+pub fn squares(bitboard: Bitboard) -> Squares {
+    Squares(bitboard)
+}
+
+pub fn positions(bitboard: Bitboard) -> impl Iterator<Item = Position> {
+    squares(bitboard).map(position_of)
+}
+
+pub fn knight_attacks(square: usize) -> Bitboard {
+    KNIGHT_ATTACKS[square]
+}
+
+pub fn king_attacks(square: usize) -> Bitboard {
+    KING_ATTACKS[square]
+}
+
+pub fn pawn_attacks(color: Color, square: usize) -> Bitboard {
+    if color == Color::White { WHITE_PAWN_ATTACKS[square] } else { BLACK_PAWN_ATTACKS[square] }
+}
+
+// Ray up to and including the first blocker
+fn ray_attacks(square: usize, occupied: Bitboard, direction: usize) -> Bitboard {
+    let ray = RAYS[direction][square];
+    let blockers = ray & occupied;
+    if blockers == 0 {
+        return ray;
+    }
+
+    let blocker = if direction.is_multiple_of(2) { blockers.trailing_zeros() } else { 63 - blockers.leading_zeros() };
+    ray ^ RAYS[direction][blocker as usize]
+}
+
+pub fn rook_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    ROOK_DIRECTIONS.iter().fold(0, |acc, direction| acc | ray_attacks(square, occupied, *direction))
+}
+
+pub fn bishop_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    BISHOP_DIRECTIONS.iter().fold(0, |acc, direction| acc | ray_attacks(square, occupied, *direction))
+}
+
+pub fn queen_attacks(square: usize, occupied: Bitboard) -> Bitboard {
+    rook_attacks(square, occupied) | bishop_attacks(square, occupied)
+}
+
+fn direction_between(from: usize, to: usize) -> Option<usize> {
+    (0..8).find(|direction| RAYS[*direction][from] & (1 << to) != 0)
+}
+
+// Squares strictly between two aligned squares, empty if they don't share a line
+pub fn between(from: usize, to: usize) -> Bitboard {
+    direction_between(from, to).map_or(0, |direction| RAYS[direction][from] & !RAYS[direction][to] & !(1 << to))
+}
+
+// Whole line through two aligned squares, empty if they don't share a line
+pub fn line(from: usize, to: usize) -> Bitboard {
+    // Opposite directions are stored next to each other
+    direction_between(from, to).map_or(0, |direction| RAYS[direction][from] | RAYS[direction ^ 1][from] | (1 << from))
+}
@@ -0,0 +1,64 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+pub const POSITION_COUNT: u16 = 960;
+
+// Knight pairs placed on the five squares left after bishops and queen
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+
+// Back rank of the start position with the given Scharnagl index, 518 being the standard one
+fn back_rank(index: u16) -> [char; 8] {
+    let mut rank = [' '; 8];
+    let index = index as usize;
+
+    rank[(index % 4) * 2 + 1] = 'b';
+    rank[((index / 4) % 4) * 2] = 'b';
+
+    let empty = |rank: &[char; 8]| -> Vec<usize> { (0..8).filter(|x| rank[*x] == ' ').collect() };
+
+    rank[empty(&rank)[(index / 16) % 6]] = 'q';
+
+    let free = empty(&rank);
+    let (first, second) = KNIGHT_PLACEMENTS[index / 96];
+    rank[free[first]] = 'n';
+    rank[free[second]] = 'n';
+
+    // King always stands between the rooks
+    for (column, piece) in empty(&rank).into_iter().zip(['r', 'k', 'r']) {
+        rank[column] = piece;
+    }
+
+    rank
+}
+
+// FEN of the start position, castling written as KQkq when the rooks stand on
+// the a and h files and as Shredder-FEN files otherwise, matching GameState::to_fen
+pub fn start_position(index: u16) -> Result<String, &'static str> {
+    if index >= POSITION_COUNT {
+        return Err("Chess960 position index must be below 960!");
+    }
+
+    let rank = back_rank(index);
+    let rook_columns: Vec<usize> = (0..8).filter(|x| rank[*x] == 'r').collect();
+    let (queen_side, king_side) = (rook_columns[0], rook_columns[1]);
+    let castles = if (queen_side, king_side) == (0, 7) {
+        "kq".to_string()
+    } else {
+        format!("{}{}", (b'a' + king_side as u8) as char, (b'a' + queen_side as u8) as char)
+    };
+
+    let black: String = rank.iter().collect();
+    Ok(format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w {}{} - 0 1",
+        black,
+        black.to_ascii_uppercase(),
+        castles.to_ascii_uppercase(),
+        castles,
+    ))
+}
+
+// Index of a random start position along with its FEN
+pub fn random_start_position() -> (u16, String) {
+    let index = (RandomState::new().build_hasher().finish() % POSITION_COUNT as u64) as u16;
+    (index, start_position(index).expect("Index is below 960"))
+}
@@ -0,0 +1,170 @@
+use super::parser::parse;
+use super::san::{parse_san, to_san};
+use super::structs::chess_move::Move;
+use super::structs::epd_error::EpdError;
+use super::structs::epd_record::EpdRecord;
+use super::structs::epd_report::{EpdReport, EpdTestResult};
+use super::structs::fen_error::FenError;
+use super::structs::game_state::GameState;
+use std::io::{self, BufRead};
+use std::mem;
+use std::str::FromStr;
+
+// Splits operations like `bm Nf3 Qd2; id "test 1";` into opcodes and operands
+fn parse_operations(input: &str) -> Result<Vec<(String, Vec<String>)>, EpdError> {
+    let mut operations: Vec<(String, Vec<String>)> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    let mut finish_operation = |words: Vec<String>| -> Result<(), EpdError> {
+        let mut words = words.into_iter();
+        match words.next() {
+            Some(opcode) if opcode.starts_with(|x: char| x.is_ascii_alphabetic()) => {
+                operations.push((opcode, words.collect()));
+                Ok(())
+            },
+            Some(opcode) => Err(EpdError::InvalidOperation(opcode)),
+            None => Err(EpdError::InvalidOperation(";".to_string())),
+        }
+    };
+
+    while let Some(letter) = chars.next() {
+        match letter {
+            ';' => finish_operation(mem::take(&mut words))?,
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(letter) => text.push(letter),
+                        None => return Err(EpdError::InvalidOperation(format!("\"{}", text))),
+                    }
+                }
+                words.push(text);
+            },
+            letter if letter.is_whitespace() => {},
+            letter => {
+                let mut word = letter.to_string();
+                while let Some(letter) = chars.next_if(|x| !x.is_whitespace() && *x != ';') {
+                    word.push(letter);
+                }
+                words.push(word);
+            },
+        }
+    }
+
+    // Tolerating a missing semicolon after the last operation
+    if !words.is_empty() {
+        finish_operation(words)?;
+    }
+
+    Ok(operations)
+}
+
+fn parse_number<T: FromStr>(opcode: &str, operands: &[String]) -> Result<T, EpdError> {
+    let operand = operands.first().map_or("", String::as_str);
+    operand.parse::<T>().map_err(|_| EpdError::InvalidOperand { opcode: opcode.to_string(), operand: operand.to_string() })
+}
+
+fn parse_moves(game_state: &GameState, opcode: &str, operands: &[String]) -> Result<Vec<Move>, EpdError> {
+    operands
+        .iter()
+        .map(|operand| {
+            parse_san(game_state, operand).map_err(|_| EpdError::InvalidOperand { opcode: opcode.to_string(), operand: operand.clone() })
+        })
+        .collect()
+}
+
+// Parses the four position fields followed by operations
+pub fn parse_epd(input: &str) -> Result<EpdRecord, EpdError> {
+    let mut rest = input.trim();
+    let mut fields: Vec<&str> = Vec::new();
+    while fields.len() < 4 && !rest.is_empty() {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+
+    if fields.len() < 4 {
+        return Err(EpdError::Fen(FenError::FieldCount(fields.len())));
+    }
+
+    let game_state = &mut GameState::new();
+    parse(&format!("{} 0 1", fields.join(" ")), game_state).map_err(EpdError::Fen)?;
+
+    let operations = parse_operations(rest)?;
+    // Move counters come first as they're part of the position the moves are played from
+    for (opcode, operands) in &operations {
+        match opcode.as_str() {
+            "hmvc" => game_state.half_moves = parse_number(opcode, operands)?,
+            "fmvn" => game_state.full_moves = parse_number(opcode, operands)?,
+            _ => {},
+        }
+    }
+
+    let mut record = EpdRecord::new(game_state.clone());
+    for (opcode, operands) in &operations {
+        match opcode.as_str() {
+            "bm" => record.best_moves = parse_moves(game_state, opcode, operands)?,
+            "am" => record.avoid_moves = parse_moves(game_state, opcode, operands)?,
+            "id" => record.id = operands.first().cloned(),
+            "dm" => record.direct_mate = Some(parse_number(opcode, operands)?),
+            "acd" => record.analysis_depth = Some(parse_number(opcode, operands)?),
+            "pv" => {
+                let variation_state = &mut game_state.clone();
+                for operand in operands {
+                    let chess_move = parse_moves(variation_state, opcode, std::slice::from_ref(operand))?.remove(0);
+                    variation_state.make_move(&chess_move);
+                    record.predicted_variation.push(chess_move);
+                }
+            },
+            _ => {
+                let index = opcode.strip_prefix('c').and_then(|x| x.parse::<usize>().ok()).filter(|x| *x < 10);
+                if let Some(index) = index {
+                    record.comments[index] = Some(operands.join(" "));
+                }
+            },
+        }
+    }
+    record.operations = operations;
+
+    Ok(record)
+}
+
+// Runs every position of the suite through the move chooser, scoring its
+// answers against the bm and am operations. Positions without either aren't scored.
+pub fn run_epd_suite<R: BufRead, F: FnMut(&EpdRecord) -> Option<Move>>(reader: R, mut choose_move: F) -> io::Result<EpdReport> {
+    let mut report = EpdReport::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = match parse_epd(&line) {
+            Ok(record) => record,
+            Err(err) => {
+                report.errors.push((index + 1, err));
+                continue;
+            },
+        };
+
+        if record.best_moves.is_empty() && record.avoid_moves.is_empty() {
+            continue;
+        }
+
+        let game_state = &record.game_state;
+        let chosen = choose_move(&record);
+        report.results.push(EpdTestResult {
+            line: index + 1,
+            id: record.id.clone(),
+            chosen: chosen.as_ref().map(|x| to_san(game_state, x)),
+            best_moves: record.best_moves.iter().map(|x| to_san(game_state, x)).collect(),
+            avoid_moves: record.avoid_moves.iter().map(|x| to_san(game_state, x)).collect(),
+            passed: chosen.is_some_and(|x| record.is_solved_by(&x)),
+        });
+    }
+
+    Ok(report)
+}
@@ -3,3 +3,11 @@ pub mod structs;
 pub mod utils;
 pub mod renderer;
 pub mod move_generator;
+pub mod bitboard;
+pub mod zobrist;
+pub mod san;
+pub mod uci;
+pub mod pgn_reader;
+pub mod pgn_writer;
+pub mod epd;
+pub mod chess960;
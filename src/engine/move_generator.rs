@@ -1,175 +1,517 @@
-use crate::engine::structs::enums::{Color, Position, PieceType};
+use crate::engine::structs::enums::{Color, Position, PieceType, Variant};
 use crate::engine::structs::piece::Piece;
+use crate::engine::structs::board_map::BoardMap;
+use crate::engine::structs::chess_move::{Move, MoveKind};
 use crate::engine::structs::game_state::GameState;
+use crate::engine::bitboard::{
+    Bitboard, bit, positions, squares, square_of, position_of, between, line, pawn_attacks,
+    knight_attacks, king_attacks, bishop_attacks, rook_attacks, queen_attacks,
+};
 use crate::engine::utils::is_in_bounds;
 use std::collections::HashSet;
 
+// First and eighth rank
+const BACK_RANKS: Bitboard = 0xFF00_0000_0000_00FF;
+
 pub fn generate_destinations(game_state: &GameState, piece: Piece, position: Position, only_attack: bool) -> HashSet<Position> {
-    match piece.piece_type {
-        PieceType::Pawn => {generate_destinations_pawn(game_state, piece, position, only_attack)},
-        PieceType::Bishop => {generate_destinations_bishop(game_state, piece, position)},
-        PieceType::Rook => {generate_destinations_rook(game_state, piece, position)},
-        PieceType::Queen => {generate_destinations_queen(game_state, piece, position)},
-        PieceType::King => {generate_destinations_king(game_state, piece, position)},
-        PieceType::Knight => {generate_destinations_knight(game_state, piece, position)},
-    }
+    positions(generate_destinations_bitboard(game_state, piece, position, only_attack)).collect()
 }
 
-#[derive(PartialEq, Debug)]
-enum DestinationsStatus {
-    Free,
-    Capture,
-    Blocked,
+// Pseudo-legal destinations, or every attacked square including own pieces when only_attack is set
+pub fn generate_destinations_bitboard(game_state: &GameState, piece: Piece, position: Position, only_attack: bool) -> Bitboard {
+    let square = square_of(position);
+    let occupied = game_state.board.occupied();
+    let attacks = match piece.piece_type {
+        PieceType::Pawn => { return generate_destinations_pawn(game_state, piece, position, only_attack) },
+        PieceType::Bishop => { bishop_attacks(square, occupied) },
+        PieceType::Rook => { rook_attacks(square, occupied) },
+        PieceType::Queen => { queen_attacks(square, occupied) },
+        PieceType::King => { king_attacks(square) },
+        PieceType::Knight => { knight_attacks(square) },
+    };
+
+    if only_attack {
+        return attacks;
+    }
+
+    // Castling is generated separately as its destination can't be told apart from a normal king move in Chess960
+    attacks & !game_state.board.by_color(piece.color)
 }
 
-fn is_possible_destination (game_state: &GameState, piece: Piece, next_position: Position) -> DestinationsStatus {
-    if !is_in_bounds(next_position) {
-        return DestinationsStatus::Blocked;
+pub fn generate_destinations_pawn(game_state: &GameState, piece: Piece, position: Position, only_attack: bool) -> Bitboard {
+    let attacks = pawn_attacks(piece.color, square_of(position));
+
+    // Pawns attack diagonal squares even when they are empty
+    if only_attack {
+        return attacks;
     }
 
-    game_state
-        .board
-        .get(&next_position)
-        .map_or_else(|| DestinationsStatus::Free, |x| if x.color == piece.color { DestinationsStatus::Blocked } else { DestinationsStatus::Capture })
+    let empty = !game_state.board.occupied();
+    let mut destinations = attacks & game_state.board.by_color(piece.color.opposite());
+    let increment = if piece.color == Color::White { -1 } else { 1 };
+    let is_double_move_allowed = (piece.color == Color::White && position.0 == 6) || (piece.color == Color::Black && position.0 == 1)
+        // Horde pawns start on the first rank as well
+        || (game_state.variant == Variant::Horde && piece.color == Color::White && position.0 == 7);
+
+    let one_step = (position.0 + increment, position.1);
+    if is_in_bounds(one_step) && empty & bit(one_step) != 0 {
+        destinations |= bit(one_step);
+
+        let two_step = (position.0 + 2*increment, position.1);
+        if is_double_move_allowed && empty & bit(two_step) != 0 {
+            destinations |= bit(two_step);
+        }
+    }
+
+    if let Some(en_pasant) = game_state.en_pasant_position {
+        if attacks & bit(en_pasant) != 0 && is_en_pasant_target(game_state, piece, position, en_pasant) {
+            destinations |= bit(en_pasant);
+        }
+    }
+
+    destinations
 }
 
-fn generate_by_ranges (game_state: &GameState, piece: Piece, position: Position, x_increment: i8, y_increment: i8) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    for i in 1..8 {
-        let next_position: Position = (position.0 + i*y_increment, position.1 + i*x_increment);
-        let move_status = is_possible_destination(game_state, piece.clone(), next_position);
-        if move_status == DestinationsStatus::Capture || move_status == DestinationsStatus::Free {
-            possible_positions.insert(next_position);
+fn is_en_pasant_target(game_state: &GameState, piece: Piece, position: Position, next_position: Position) -> bool {
+    // En pasant square is always on the 6th rank from the capturing side's point of view
+    let target_row = if piece.color == Color::White { 2 } else { 5 };
+
+    game_state.en_pasant_position == Some(next_position)
+        && next_position.0 == target_row
+        && !game_state.board.contains_key(&next_position)
+        && game_state
+            .board
+            .get(&(position.0, next_position.1))
+            .is_some_and(|x| x.color != piece.color && x.piece_type == PieceType::Pawn)
+}
+
+// Castling moves of the king on its back rank. The king lands on the g or c file
+// and the rook next to it on the f or d file, wherever they started in Chess960.
+pub fn generate_castles(game_state: &GameState, piece: Piece, position: Position) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::new();
+    let color = piece.color;
+    let row = if color == Color::White { 7 } else { 0 };
+    let board = &game_state.board;
+    let king = square_of(position);
+
+    // Touching kings change what counts as attacked in Atomic, so there the
+    // king's safety is verified along with the other Atomic moves
+    let is_atomic = game_state.variant == Variant::Atomic;
+
+    // King can't castle out of check, and there's no castling in Antichess
+    if position.0 != row || game_state.variant == Variant::Antichess || (!is_atomic && is_square_attacked(board, king, color.opposite())) {
+        return moves;
+    }
+
+    let sides = [
+        (game_state.castles.king_side_rook(color), MoveKind::KingSideCastle, 6, 5),
+        (game_state.castles.queen_side_rook(color), MoveKind::QueenSideCastle, 2, 3),
+    ];
+
+    for (rook_column, kind, king_column, rook_to_column) in sides {
+        let rook_column = match rook_column {
+            Some(column) if (column > position.1) == (kind == MoveKind::KingSideCastle) => column,
+            _ => continue,
+        };
+
+        if board.get(&(row, rook_column)) != Some(Piece::new(PieceType::Rook, color)) {
+            continue;
+        }
+
+        let rook = square_of((row, rook_column));
+        let king_to = square_of((row, king_column));
+        let rook_to = square_of((row, rook_to_column));
+
+        // Both pieces need free paths, apart from each other
+        let occupied = board.occupied() & !(1 << king) & !(1 << rook);
+        let king_path = between(king, king_to) | (1 << king_to);
+        if (king_path | between(rook, rook_to) | (1 << rook_to)) & occupied != 0 {
+            continue;
         }
 
-        if move_status == DestinationsStatus::Capture || move_status == DestinationsStatus::Blocked {
-            break;
+        // King may not pass through or land on an attacked square
+        if !is_atomic && squares(king_path).any(|square| attackers_bitboard(board, square, color.opposite(), occupied) != 0) {
+            continue;
         }
+
+        let mut chess_move = Move::new(position, (row, king_column), piece);
+        chess_move.kind = kind;
+        chess_move.castle_rook = Some((row, rook_column));
+        moves.push(chess_move);
     }
 
-    possible_positions
+    moves
 }
 
-pub fn generate_destinations_pawn(game_state: &GameState, piece: Piece, position: Position, only_attack: bool) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    let increment = if piece.color == Color::White { -1 } else { 1 };
-    let is_double_move_allowed = (piece.color == Color::White && position.0 == 6) || (piece.color == Color::Black && position.0 == 1);
-
-    if !only_attack {
-        let one_step = (position.0 + increment, position.1);
-        let move_status = is_possible_destination(game_state, piece.clone(), one_step);
-        if move_status == DestinationsStatus::Free {
-            possible_positions.insert(one_step);
-
-            if is_double_move_allowed {
-                let two_step = (position.0 + 2*increment, position.1);
-                let move_status = is_possible_destination(game_state, piece.clone(), two_step);
-                if move_status == DestinationsStatus::Free {
-                    possible_positions.insert(two_step);
-                }
+// Pieces of the given color attacking the square, sliders see through nothing but `occupied`
+pub fn attackers_bitboard(board: &BoardMap, square: usize, color: Color, occupied: Bitboard) -> Bitboard {
+    let diagonal = board.pieces(PieceType::Bishop, color) | board.pieces(PieceType::Queen, color);
+    let straight = board.pieces(PieceType::Rook, color) | board.pieces(PieceType::Queen, color);
+
+    (pawn_attacks(color.opposite(), square) & board.pieces(PieceType::Pawn, color))
+        | (knight_attacks(square) & board.pieces(PieceType::Knight, color))
+        | (king_attacks(square) & board.pieces(PieceType::King, color))
+        | (bishop_attacks(square, occupied) & diagonal)
+        | (rook_attacks(square, occupied) & straight)
+}
+
+pub fn is_square_attacked(board: &BoardMap, square: usize, color: Color) -> bool {
+    attackers_bitboard(board, square, color, board.occupied()) != 0
+}
+
+// Every square attacked by the pieces of the given color
+pub fn attacked_bitboard(game_state: &GameState, color: Color) -> Bitboard {
+    game_state
+        .board
+        .iter()
+        .filter(|(_, piece)| piece.color == color)
+        .fold(0, |acc, (pos, piece)| acc | generate_destinations_bitboard(game_state, piece, pos, true))
+}
+
+// Squares attacked by the opponents of the given color
+pub fn generate_attacked_fields(game_state: &GameState, color: Color) -> HashSet<Position> {
+    positions(attacked_bitboard(game_state, color.opposite())).collect()
+}
+
+// Check and pin state of one side, shared by the legality checks of all its pieces
+pub struct CheckInfo {
+    pub king: Option<usize>,
+    pub checkers: Bitboard,
+    pub pinned: Bitboard,
+}
+
+// Pinned pieces of the given color paired with the enemy sliders pinning them
+pub fn pins(board: &BoardMap, color: Color) -> Vec<(usize, usize)> {
+    let king = match board.king_square(color) {
+        Some(king) => king,
+        None => return Vec::new(),
+    };
+
+    let opponent = color.opposite();
+    let queens = board.pieces(PieceType::Queen, opponent);
+    let snipers = (rook_attacks(king, 0) & (board.pieces(PieceType::Rook, opponent) | queens))
+        | (bishop_attacks(king, 0) & (board.pieces(PieceType::Bishop, opponent) | queens));
+
+    squares(snipers)
+        .filter_map(|sniper| {
+            let blockers = between(king, sniper) & board.occupied();
+            if blockers.count_ones() == 1 && blockers & board.by_color(color) != 0 {
+                Some((blockers.trailing_zeros() as usize, sniper))
+            } else {
+                None
             }
+        })
+        .collect()
+}
+
+pub fn check_info(board: &BoardMap, color: Color) -> CheckInfo {
+    match board.king_square(color) {
+        Some(king) => CheckInfo {
+            king: Some(king),
+            checkers: attackers_bitboard(board, king, color.opposite(), board.occupied()),
+            pinned: pins(board, color).iter().fold(0, |acc, (pinned, _)| acc | (1 << pinned)),
+        },
+        None => CheckInfo { king: None, checkers: 0, pinned: 0 },
+    }
+}
+
+fn legal_destinations(game_state: &GameState, piece: Piece, position: Position, info: &CheckInfo) -> Bitboard {
+    let destinations = generate_destinations_bitboard(game_state, piece, position, false);
+    let king = match info.king {
+        Some(king) => king,
+        None => return destinations,
+    };
+
+    let board = &game_state.board;
+    let opponent = piece.color.opposite();
+
+    // King may not step onto an attacked square, including squares behind it on a checking line
+    if piece.piece_type == PieceType::King {
+        let occupied = board.occupied() ^ bit(position);
+        return squares(destinations)
+            .filter(|destination| attackers_bitboard(board, *destination, opponent, occupied) == 0)
+            .fold(0, |acc, destination| acc | (1 << destination));
+    }
+
+    // Only the king can escape a double check
+    if info.checkers.count_ones() > 1 {
+        return 0;
+    }
+
+    // En pasant removes two pieces from the rank, so it is verified on the resulting board
+    let en_pasant = match game_state.en_pasant_position {
+        Some(en_pasant) if piece.piece_type == PieceType::Pawn => destinations & bit(en_pasant),
+        _ => 0,
+    };
+
+    let mut valid_destinations = destinations & !en_pasant;
+
+    if info.checkers != 0 {
+        let checker = info.checkers.trailing_zeros() as usize;
+        valid_destinations &= info.checkers | between(king, checker);
+    }
+
+    if info.pinned & bit(position) != 0 {
+        valid_destinations &= line(king, square_of(position));
+    }
+
+    if en_pasant != 0 {
+        let board_after = game_state.board_after(position, position_of(en_pasant.trailing_zeros() as usize));
+        if !is_square_attacked(&board_after, king, opponent) {
+            valid_destinations |= en_pasant;
         }
     }
 
-    for i in [-1,1] {
-        let next_position = (position.0 + increment, position.1 + i);
-        let move_status = is_possible_destination(game_state, piece.clone(), next_position);
-        if move_status == DestinationsStatus::Capture {
-            possible_positions.insert(next_position);
+    valid_destinations
+}
+
+// Legal destinations including the king's destination when castling
+pub fn generate_valid_destinations_bitboard(game_state: &GameState, piece: Piece, position: Position) -> Bitboard {
+    if matches!(game_state.variant, Variant::Atomic | Variant::Antichess | Variant::RacingKings) {
+        return generate_moves(game_state, piece, position).iter().fold(0, |acc, x| acc | bit(x.to));
+    }
+
+    let destinations = legal_destinations(game_state, piece, position, &check_info(&game_state.board, piece.color));
+    if piece.piece_type != PieceType::King {
+        return destinations;
+    }
+
+    generate_castles(game_state, piece, position).iter().fold(destinations, |acc, x| acc | bit(x.to))
+}
+
+pub fn generate_valid_destinations(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
+    positions(generate_valid_destinations_bitboard(game_state, piece, position)).collect()
+}
+
+pub fn generate_moves(game_state: &GameState, piece: Piece, position: Position) -> Vec<Move> {
+    // Whether the piece has to capture depends on every other piece
+    if game_state.variant == Variant::Antichess {
+        return generate_all_moves(game_state, piece.color).into_iter().filter(|x| x.from == position).collect();
+    }
+
+    let mut moves: Vec<Move> = Vec::new();
+    add_piece_moves(&mut moves, game_state, piece, position, &check_info(&game_state.board, piece.color));
+    moves
+}
+
+// Legal moves of every piece of the given color, including drops
+pub fn generate_all_moves(game_state: &GameState, color: Color) -> Vec<Move> {
+    if game_state.variant == Variant::Antichess {
+        return forced_captures(generate_pseudo_legal_moves(game_state, color));
+    }
+
+    let mut moves: Vec<Move> = Vec::new();
+    let info = check_info(&game_state.board, color);
+    for (position, piece) in game_state.board.iter().filter(|(_, piece)| piece.color == color) {
+        add_piece_moves(&mut moves, game_state, piece, position, &info);
+    }
+    moves.extend(generate_drops(game_state, color));
+    moves
+}
+
+// Moves of every piece of the given color, regardless of whether they leave its king attacked
+pub fn generate_pseudo_legal_moves(game_state: &GameState, color: Color) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::new();
+    for (position, piece) in game_state.board.iter().filter(|(_, piece)| piece.color == color) {
+        add_moves(&mut moves, game_state, piece, position, generate_destinations_bitboard(game_state, piece, position, false));
+    }
+    moves
+}
+
+// Only the captures when there are any, as capturing is compulsory in Antichess
+pub fn forced_captures(moves: Vec<Move>) -> Vec<Move> {
+    if moves.iter().any(Move::is_capture) {
+        moves.into_iter().filter(Move::is_capture).collect()
+    } else {
+        moves
+    }
+}
+
+// Crazyhouse drops from the pocket onto empty squares, pawns not on the first or last rank
+pub fn generate_drops(game_state: &GameState, color: Color) -> Vec<Move> {
+    let mut moves: Vec<Move> = Vec::new();
+    let pocket = &game_state.pockets[color as usize];
+    if game_state.variant != Variant::Crazyhouse || pocket.is_empty() {
+        return moves;
+    }
+
+    let info = check_info(&game_state.board, color);
+    let mut targets = !game_state.board.occupied();
+
+    // Dropped piece can only answer a check by blocking it
+    if let Some(king) = info.king {
+        match info.checkers.count_ones() {
+            0 => {},
+            1 => { targets &= between(king, info.checkers.trailing_zeros() as usize) },
+            _ => { targets = 0 },
         }
-        match game_state.en_pasant_position {
-            Some(_) => {
-                if move_status == DestinationsStatus::Free {
-                    possible_positions.insert(next_position);
-                }
-            },
-            None => {},
+    }
+
+    for piece_type in pocket.piece_types() {
+        let piece = Piece::new(piece_type, color);
+        let mask = if piece_type == PieceType::Pawn { targets & !BACK_RANKS } else { targets };
+        for position in positions(mask) {
+            let mut chess_move = Move::new(position, position, piece);
+            chess_move.kind = MoveKind::Drop;
+            moves.push(chess_move);
         }
     }
 
-    possible_positions
+    moves
 }
 
-pub fn generate_destinations_rook(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, 1, 0));
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, -1, 0));
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, 0, 1));
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, 0, -1));
-    possible_positions
+fn add_piece_moves(moves: &mut Vec<Move>, game_state: &GameState, piece: Piece, position: Position, info: &CheckInfo) {
+    match game_state.variant {
+        Variant::Atomic => add_atomic_moves(moves, game_state, piece, position),
+        Variant::RacingKings => {
+            let mut candidates: Vec<Move> = Vec::new();
+            add_moves(&mut candidates, game_state, piece, position, legal_destinations(game_state, piece, position, info));
+            moves.extend(candidates.into_iter().filter(|x| !gives_check(game_state, x)));
+        },
+        _ => add_moves(moves, game_state, piece, position, legal_destinations(game_state, piece, position, info)),
+    }
 }
 
-pub fn generate_destinations_bishop(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, 1, 1));
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, 1, -1));
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, -1, 1));
-    possible_positions.extend(&generate_by_ranges(game_state, piece.clone(), position, -1, -1));
-    possible_positions
+// Whether the move attacks the enemy king, which is never allowed in Racing Kings
+fn gives_check(game_state: &GameState, chess_move: &Move) -> bool {
+    let color = chess_move.piece.color;
+    let mut board = game_state.board_after(chess_move.from, chess_move.to);
+    if let Some(promotion) = chess_move.promotion {
+        board.insert(chess_move.to, Piece::new(promotion, color));
+    }
+
+    board.king_square(color.opposite()).is_some_and(|king| is_square_attacked(&board, king, color))
 }
 
-pub fn generate_destinations_queen(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    possible_positions.extend(&generate_destinations_bishop(game_state, piece.clone(), position));
-    possible_positions.extend(&generate_destinations_rook(game_state, piece.clone(), position));
-    possible_positions
+// Pieces blown up along with the capturing one when a capture lands on the square: everything but pawns around it
+pub fn explosion(board: &BoardMap, square: usize) -> Bitboard {
+    king_attacks(square) & board.occupied() & !board.by_type(PieceType::Pawn)
 }
 
-pub fn generate_destinations_king(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    for i in -1..2 {
-        for j in -1..2 {
-            if i != 0 || j != 0 {
-                let next_position = (position.0 + i, position.1 + j);
-                let move_status = is_possible_destination(game_state, piece.clone(), next_position);
+// Board left by an Atomic move, where a capture removes the capturing piece as well
+pub fn atomic_board_after(game_state: &GameState, chess_move: &Move) -> BoardMap {
+    let mut board = game_state.board;
+    board.remove(&chess_move.from);
 
-                if move_status == DestinationsStatus::Capture || move_status == DestinationsStatus::Free {
-                    possible_positions.insert(next_position);
-                }
+    match chess_move.capture_position() {
+        Some(captured) => {
+            board.remove(&captured);
+            for position in positions(explosion(&board, square_of(chess_move.to))) {
+                board.remove(&position);
             }
-        }
+        },
+        None => {
+            let castle_rook = chess_move
+                .castle_rook_positions()
+                .and_then(|(rook_from, rook_to)| board.remove(&rook_from).map(|rook| (rook_to, rook)));
+
+            let piece = chess_move.promotion.map_or(chess_move.piece, |x| Piece::new(x, chess_move.piece.color));
+            board.insert(chess_move.to, piece);
+
+            if let Some((rook_to, rook)) = castle_rook {
+                board.insert(rook_to, rook);
+            }
+        },
     }
-    possible_positions
+
+    board
 }
 
-pub fn generate_destinations_knight(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
-    let mut possible_positions: HashSet<Position> = HashSet::new();
-    for i in -2..3 {
-        for j in -2..3 {
-            if (i as i8).abs() + (j as i8).abs() == 3 {
-                let next_position = (position.0 + i, position.1 + j);
-                let move_status = is_possible_destination(game_state, piece.clone(), next_position);
-                if move_status == DestinationsStatus::Capture || move_status == DestinationsStatus::Free {
-                    possible_positions.insert(next_position);
-                }
-            }
-        }
+// Atomic check: kings standing next to each other can't be attacked, since capturing
+// one would blow up the other
+pub fn is_atomic_check(board: &BoardMap, color: Color) -> bool {
+    match (board.king_square(color), board.king_square(color.opposite())) {
+        (Some(king), Some(enemy_king)) => king_attacks(king) & (1 << enemy_king) == 0 && is_square_attacked(board, king, color.opposite()),
+        _ => false,
     }
-    possible_positions
 }
 
-pub fn generate_attacked_fields(game_state: &GameState, color: Color) -> HashSet<Position> {
-    let mut attacked_fields: HashSet<Position> = HashSet::new();
-    game_state.board.clone().iter().filter(|(_, piece)| piece.color != color).for_each(|(pos, piece)| {
-        attacked_fields.extend(&generate_destinations(game_state, piece.clone(), pos.clone(), true));
+// Castling king may not start, pass or land in check, while standing next to the enemy king is always safe
+fn is_legal_atomic_castle(game_state: &GameState, chess_move: &Move) -> bool {
+    let color = chess_move.piece.color;
+    if is_atomic_check(&game_state.board, color) {
+        return false;
+    }
+
+    // Squares passed are judged in the current position, where the castling rook may still shield them
+    let mut lifted = game_state.board;
+    lifted.remove(&chess_move.from);
+
+    let (from, to) = (square_of(chess_move.from), square_of(chess_move.to));
+    let is_path_safe = squares(between(from, to)).all(|square| {
+        let mut board = lifted;
+        board.insert(position_of(square), chess_move.piece);
+        !is_atomic_check(&board, color)
     });
-    attacked_fields
+
+    is_path_safe && !is_atomic_check(&atomic_board_after(game_state, chess_move), color)
 }
 
-pub fn generate_valid_destinations(game_state: &GameState, piece: Piece, position: Position) -> HashSet<Position> {
-    let mut all_moves = generate_destinations(game_state, piece.clone(), position, false);
-    let mut illegal_moves: HashSet<Position> = HashSet::new();
-    let color = piece.color;
-    for possible_move in all_moves.iter() {
-        let new_board = &game_state.fake_move(position, *possible_move);
-        let attacked_fields = generate_attacked_fields(new_board, color);
-        if let Some(king_pos) = new_board.find_king(piece.color) {
-            if attacked_fields.contains(&king_pos) {
-                illegal_moves.insert(*possible_move);
+fn is_legal_atomic(game_state: &GameState, chess_move: &Move) -> bool {
+    if chess_move.is_castle() {
+        return is_legal_atomic_castle(game_state, chess_move);
+    }
+
+    let color = chess_move.piece.color;
+    let board = atomic_board_after(game_state, chess_move);
+
+    match (board.king_square(color), board.king_square(color.opposite())) {
+        (None, _) => false,
+        // Blowing up the enemy king wins at once, even if the own king is left in check
+        (Some(_), None) => true,
+        (Some(_), Some(_)) => !is_atomic_check(&board, color),
+    }
+}
+
+// Explosions may take away pinning or checking pieces, so Atomic moves are verified
+// on the board left after them instead of through pins and checkers
+fn add_atomic_moves(moves: &mut Vec<Move>, game_state: &GameState, piece: Piece, position: Position) {
+    let mut destinations = generate_destinations_bitboard(game_state, piece, position, false);
+
+    // King would blow itself up by capturing
+    if piece.piece_type == PieceType::King {
+        destinations &= !game_state.board.occupied();
+    }
+
+    let mut candidates: Vec<Move> = Vec::new();
+    add_moves(&mut candidates, game_state, piece, position, destinations);
+    moves.extend(candidates.into_iter().filter(|x| is_legal_atomic(game_state, x)));
+}
+
+// Moves to the given destinations, with promotions expanded and castling added for the king
+fn add_moves(moves: &mut Vec<Move>, game_state: &GameState, piece: Piece, position: Position, destinations: Bitboard) {
+    let last_row = if piece.color == Color::White { 0 } else { 7 };
+
+    // King is an ordinary piece pawns may promote to in Antichess
+    let promotions: &[PieceType] = if game_state.variant == Variant::Antichess {
+        &[PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight, PieceType::King]
+    } else {
+        &[PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight]
+    };
+
+    for destination in positions(destinations) {
+        let mut chess_move = Move::new(position, destination, piece);
+        chess_move.captured = game_state.board.get(&destination);
+
+        if let Some(captured) = game_state.en_pasant_capture(position, destination) {
+            chess_move.captured = game_state.board.get(&captured);
+            chess_move.kind = MoveKind::EnPasant;
+        } else if piece.piece_type == PieceType::Pawn && (destination.0 - position.0).abs() == 2 {
+            chess_move.kind = MoveKind::DoublePush;
+        }
+
+        if piece.piece_type == PieceType::Pawn && destination.0 == last_row {
+            for promotion in promotions {
+                let mut promotion_move = chess_move.clone();
+                promotion_move.promotion = Some(*promotion);
+                moves.push(promotion_move);
             }
+        } else {
+            moves.push(chess_move);
         }
     }
 
-    all_moves.retain(|x| !illegal_moves.contains(x));
-    all_moves
+    if piece.piece_type == PieceType::King {
+        moves.extend(generate_castles(game_state, piece, position));
+    }
 }
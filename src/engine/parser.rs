@@ -1,13 +1,19 @@
 use super::structs::piece::Piece;
+use super::structs::board_map::BoardMap;
 use super::structs::castles_state::CastlesState;
-use super::structs::enums::{Color, Position};
+use super::structs::enums::{Color, Position, PieceType, Variant};
+use super::structs::fen_error::{FenError, FenField};
+use super::structs::pocket::MAX_COUNT;
 use super::structs::game_state::GameState;
 use super::utils::std_pos_to_couple;
+use super::bitboard::bit;
+
+pub const INITIAL_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 pub fn parse_move(input: String) -> Result<(Position, Position, Option<Piece>), &'static str> {
     let split: &mut Vec<String> = &mut input.trim().split(":").map(str::to_string).collect();
 
-    if split.len() != 2 {
+    if split.len() != 2 && split.len() != 3 {
         return Err("Invalid move!");
     }
 
@@ -17,62 +23,214 @@ pub fn parse_move(input: String) -> Result<(Position, Position, Option<Piece>),
     let to = std_pos_to_couple(to);
     let promotion_piece = if split.len() == 1 {
         let promotion = split.remove(0);
-        let fen_char = promotion.as_bytes()[0] as char;
-        Some(Piece::from_fen_char(fen_char))
+        let fen_char = promotion.chars().next().ok_or("Invalid promotion piece!")?;
+        Some(Piece::from_fen_char(fen_char).ok_or("Invalid promotion piece!")?)
     } else {
         None
     };
 
 
     from.and_then(
-        |f| to.and_then(
-            |t| Ok((f, t, promotion_piece))
+        |f| to.map(
+            |t| (f, t, promotion_piece)
         )
     )
 }
 
-fn parse_position(position_string: String, state: &mut GameState) {
-    let rows: Vec<String> = position_string
-        .split("/")
-        .map(str::to_string)
-        .collect();
+fn parse_position(position_string: &str, state: &mut GameState) -> Result<(), FenError> {
+    let rows: Vec<&str> = position_string.split('/').collect();
+
+    if rows.len() != 8 {
+        return Err(FenError::RankCount(rows.len()));
+    }
 
     for (row_index, row) in rows.iter().enumerate() {
-        let mut current_column: i8 = 0;
+        let mut current_column: usize = 0;
         for letter in row.chars() {
             match letter.to_digit(10) {
-                Some(value) => {
-                    current_column += (value - 1) as i8;
+                Some(value) if (1..=8).contains(&value) => {
+                    current_column += value as usize;
+                },
+                Some(_) => {
+                    return Err(FenError::InvalidCharacter { field: FenField::Position, character: letter });
+                },
+                // Crazyhouse marks promoted pieces with a tilde after them
+                None if letter == '~' => {
+                    let previous: Position = (row_index as i8, current_column as i8 - 1);
+                    if current_column == 0 || current_column > 8 || !state.board.contains_key(&previous) {
+                        return Err(FenError::InvalidCharacter { field: FenField::Position, character: letter });
+                    }
+                    state.promoted |= bit(previous);
                 },
                 None => {
-                    let pos: Position = (row_index as i8, current_column);
-                    let piece = Piece::from_fen_char(letter);
-                    state.board.insert(pos, piece);
+                    let piece = Piece::from_fen_char(letter)
+                        .ok_or(FenError::InvalidCharacter { field: FenField::Position, character: letter })?;
+                    if current_column < 8 {
+                        let pos: Position = (row_index as i8, current_column as i8);
+                        state.board.insert(pos, piece);
+                    }
+                    current_column += 1;
                 }
             }
-            current_column += 1;
         }
+
+        if current_column != 8 {
+            return Err(FenError::RankLength { rank: 8 - row_index, files: current_column });
+        }
+    }
+
+    Ok(())
+}
+
+// Crazyhouse pocket given in brackets after the placement, e.g. [QPnn]
+fn parse_pocket(pocket: &str, state: &mut GameState) -> Result<(), FenError> {
+    for letter in pocket.chars() {
+        let piece = match Piece::from_fen_char(letter) {
+            Some(piece) if piece.piece_type != PieceType::King => piece,
+            _ => return Err(FenError::InvalidCharacter { field: FenField::Position, character: letter }),
+        };
+
+        let hand = &mut state.pockets[piece.color as usize];
+        if hand.count(piece.piece_type) >= MAX_COUNT {
+            return Err(FenError::InvalidValue { field: FenField::Position, value: pocket.to_string() });
+        }
+        hand.add(piece.piece_type);
+    }
+
+    Ok(())
+}
+
+// Accepts X-FEN KQkq as well as Shredder-FEN rook files for Chess960
+fn parse_castles(castles_state: &str, board: &BoardMap) -> Result<CastlesState, FenError> {
+    if castles_state == "-" {
+        return Ok(CastlesState::new());
+    }
+
+    let mut seen: Vec<char> = Vec::new();
+    for letter in castles_state.chars() {
+        if !"KQkqABCDEFGHabcdefgh".contains(letter) || seen.contains(&letter) {
+            return Err(FenError::InvalidCharacter { field: FenField::Castles, character: letter });
+        }
+        seen.push(letter);
+    }
+
+    Ok(CastlesState::from_fen_str(castles_state, board))
+}
+
+fn parse_counter(counter: &str, field: FenField) -> Result<u16, FenError> {
+    counter.parse::<u16>().map_err(|_| FenError::InvalidValue { field, value: counter.to_string() })
+}
+
+// Three-check counters of checks given, White's first, e.g. +2+0
+fn parse_checks(checks: &str) -> Result<[u8; 2], FenError> {
+    let invalid = || FenError::InvalidValue { field: FenField::Checks, value: checks.to_string() };
+    let (white, black) = checks.strip_prefix('+').and_then(|x| x.split_once('+')).ok_or_else(invalid)?;
+
+    let mut counters = [0; 2];
+    for (color, count) in [(Color::White, white), (Color::Black, black)] {
+        counters[color as usize] = count.parse::<u8>().ok().filter(|x| *x <= 3).ok_or_else(invalid)?;
     }
+
+    Ok(counters)
 }
 
-pub fn parse(input: &mut String, state: &mut GameState) {
-    let split: &mut Vec<String> = &mut input.split(" ").map(str::to_string).collect();
+pub fn parse(input: &str, state: &mut GameState) -> Result<(), FenError> {
+    let split: Vec<&str> = input.split_whitespace().collect();
+
+    // Three-check positions carry the check counters as a seventh field
+    if split.len() != 6 && !(split.len() == 7 && split[6].starts_with('+')) {
+        return Err(FenError::FieldCount(split.len()));
+    }
+
+    let mut new_state = GameState::new();
 
-    if split.len() != 6 {
-        panic!("Invalid FEN input provided!");
+    let (placement, pocket) = match split[0].split_once('[') {
+        Some((placement, pocket)) => {
+            let pocket = pocket.strip_suffix(']').ok_or(FenError::InvalidCharacter { field: FenField::Position, character: '[' })?;
+            (placement, Some(pocket))
+        },
+        None => (split[0], None),
+    };
+
+    parse_position(placement, &mut new_state)?;
+    if let Some(pocket) = pocket {
+        new_state.variant = Variant::Crazyhouse;
+        parse_pocket(pocket, &mut new_state)?;
+    }
+    new_state.whose_move = match split[1] {
+        "w" => Color::White,
+        "b" => Color::Black,
+        value => return Err(FenError::InvalidValue { field: FenField::WhoseMove, value: value.to_string() }),
+    };
+    new_state.castles = parse_castles(split[2], &new_state.board)?;
+    new_state.en_pasant_position = match split[3] {
+        "-" => None,
+        value => Some(
+            std_pos_to_couple(value.to_string())
+                .map_err(|_| FenError::InvalidValue { field: FenField::EnPasant, value: value.to_string() })?
+        ),
+    };
+    new_state.half_moves = parse_counter(split[4], FenField::HalfMoves)?;
+    new_state.full_moves = parse_counter(split[5], FenField::FullMoves)?;
+    if let Some(checks) = split.get(6) {
+        new_state.variant = Variant::ThreeCheck;
+        new_state.checks = parse_checks(checks)?;
     }
 
-    let position = split.remove(0);
-    let whose_move = split.remove(0);
-    let castles_state = split.remove(0);
-    let en_pasant_position = split.remove(0);
-    let half_moves = split.remove(0);
-    let full_moves = split.remove(0);
-
-    parse_position(position, state);
-    state.whose_move = if whose_move == "w" { Color::White } else { Color::Black };
-    state.castles = CastlesState::from_fen_str(castles_state);
-    state.en_pasant_position = if en_pasant_position == "-" { None } else { std_pos_to_couple(en_pasant_position).ok() };
-    state.half_moves = half_moves.parse::<u16>().unwrap();
-    state.full_moves = full_moves.parse::<u16>().unwrap();
+    *state = new_state;
+
+    Ok(())
+}
+
+// Checks that the parsed position could occur in a game
+pub fn validate_position(state: &GameState) -> Result<(), FenError> {
+    for color in [Color::White, Color::Black] {
+        let count = state.board.pieces(PieceType::King, color).count_ones();
+        // Kings can be captured and promoted to in Antichess, and the Horde has none
+        let is_kingless = state.variant == Variant::Antichess || (state.variant == Variant::Horde && color == Color::White);
+        if count != 1 && !is_kingless {
+            return Err(FenError::KingCount { color, count });
+        }
+    }
+
+    if let Some((position, _)) = state.board
+        .iter()
+        .find(|(position, piece)| {
+            // Horde pawns may stand on the first rank
+            let is_horde_pawn = state.variant == Variant::Horde && piece.color == Color::White && position.0 == 7;
+            piece.piece_type == PieceType::Pawn && (position.0 == 0 || position.0 == 7) && !is_horde_pawn
+        })
+    {
+        return Err(FenError::PawnOnBackRank(position));
+    }
+
+    if state.is_in_check(state.whose_move.opposite()) {
+        return Err(FenError::OpponentInCheck);
+    }
+
+    if let Some(en_pasant) = state.en_pasant_position {
+        // Pawn of the side not to move has just passed the square with a double push
+        let (row, direction) = if state.whose_move == Color::White { (2, 1) } else { (5, -1) };
+        let pawn = Piece::new(PieceType::Pawn, state.whose_move.opposite());
+        let is_consistent = en_pasant.0 == row
+            && !state.board.contains_key(&en_pasant)
+            && !state.board.contains_key(&(en_pasant.0 - direction, en_pasant.1))
+            && state.board.get(&(en_pasant.0 + direction, en_pasant.1)) == Some(pawn);
+
+        if !is_consistent {
+            return Err(FenError::InvalidEnPasant(en_pasant));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn parse_validated(input: &str, state: &mut GameState) -> Result<(), FenError> {
+    let mut new_state = GameState::new();
+    parse(input, &mut new_state)?;
+    validate_position(&new_state)?;
+
+    *state = new_state;
+
+    Ok(())
 }
@@ -0,0 +1,386 @@
+use super::parser::{parse, INITIAL_FEN};
+use super::san::parse_san;
+use super::structs::enums::Variant;
+use super::structs::game_state::GameState;
+use super::structs::pgn_error::PgnError;
+use super::structs::pgn_game::{PgnGame, PgnMove};
+use std::io::BufRead;
+use std::mem;
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Tag(String, String),
+    Comment(String),
+    Nag(u8),
+    VariationStart,
+    VariationEnd,
+    MoveNumber,
+    GameResult(String),
+    San(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+fn is_symbol_char(letter: char) -> bool {
+    letter.is_ascii_alphanumeric() || "_+#=:-/@".contains(letter)
+}
+
+fn suffix_to_nag(suffix: &str) -> Option<u8> {
+    match suffix {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    }
+}
+
+// Splits the input into tokens, reading it lazily line by line
+struct Lexer<R: BufRead> {
+    reader: R,
+    buffer: Vec<char>,
+    index: usize,
+    line: usize,
+    eof: bool,
+}
+
+impl<R: BufRead> Lexer<R> {
+    fn new(reader: R) -> Lexer<R> {
+        Lexer { reader, buffer: Vec::new(), index: 0, line: 0, eof: false }
+    }
+
+    fn error(&self, message: &str) -> PgnError {
+        PgnError::new(self.line, self.index + 1, message.to_string())
+    }
+
+    fn peek_char(&mut self) -> Result<Option<char>, PgnError> {
+        while self.index >= self.buffer.len() {
+            if self.eof {
+                return Ok(None);
+            }
+
+            let mut bytes = Vec::new();
+            match self.reader.read_until(b'\n', &mut bytes) {
+                Ok(0) => { self.eof = true },
+                Ok(_) => {
+                    self.index = 0;
+                    self.line += 1;
+
+                    match String::from_utf8(bytes) {
+                        Ok(line) => self.buffer = line.chars().collect(),
+                        Err(err) => {
+                            // The line is dropped, the rest of its game gets skipped after the error
+                            let valid = &err.as_bytes()[..err.utf8_error().valid_up_to()];
+                            let column = String::from_utf8_lossy(valid).chars().count() + 1;
+                            self.buffer.clear();
+                            return Err(PgnError::new(self.line, column, "Invalid UTF-8".to_string()));
+                        },
+                    }
+
+                    // Escape mechanism, lines starting with % are ignored
+                    if self.buffer.first() == Some(&'%') {
+                        self.buffer.clear();
+                    }
+                },
+                Err(err) => {
+                    // Failed reads are not retried, the input ends with the error
+                    self.eof = true;
+                    return Err(self.error(&err.to_string()));
+                },
+            }
+        }
+
+        Ok(Some(self.buffer[self.index]))
+    }
+
+    fn next_char(&mut self) -> Result<Option<char>, PgnError> {
+        let letter = self.peek_char()?;
+        if letter.is_some() {
+            self.index += 1;
+        }
+        Ok(letter)
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), PgnError> {
+        while self.peek_char()?.is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+        Ok(())
+    }
+
+    fn read_while(&mut self, predicate: fn(char) -> bool) -> Result<String, PgnError> {
+        let mut text = String::new();
+        while let Some(letter) = self.peek_char()?.filter(|x| predicate(*x)) {
+            text.push(letter);
+            self.index += 1;
+        }
+        Ok(text)
+    }
+
+    fn read_tag(&mut self) -> Result<TokenKind, PgnError> {
+        self.skip_whitespace()?;
+        let name = self.read_while(is_symbol_char)?;
+        if name.is_empty() {
+            return Err(self.error("Expected tag name"));
+        }
+
+        self.skip_whitespace()?;
+        if self.next_char()? != Some('"') {
+            return Err(self.error("Expected tag value"));
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.next_char()? {
+                Some('\\') => match self.next_char()? {
+                    Some(letter) => value.push(letter),
+                    None => return Err(self.error("Unterminated tag value")),
+                },
+                Some('"') => break,
+                Some('\n') | None => return Err(self.error("Unterminated tag value")),
+                Some(letter) => value.push(letter),
+            }
+        }
+
+        self.skip_whitespace()?;
+        if self.next_char()? != Some(']') {
+            return Err(self.error("Expected ']'"));
+        }
+
+        Ok(TokenKind::Tag(name, value))
+    }
+
+    fn read_comment(&mut self) -> Result<TokenKind, PgnError> {
+        let mut comment = String::new();
+        loop {
+            match self.next_char()? {
+                Some('}') => break,
+                Some(letter) => comment.push(letter),
+                None => return Err(self.error("Unterminated comment")),
+            }
+        }
+
+        Ok(TokenKind::Comment(comment.split_whitespace().collect::<Vec<&str>>().join(" ")))
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, PgnError> {
+        // Periods only follow move numbers and carry no information
+        while self.peek_char()?.is_some_and(|x| x.is_whitespace() || x == '.') {
+            self.index += 1;
+        }
+
+        let (line, column) = (self.line, self.index + 1);
+        let letter = match self.next_char()? {
+            Some(letter) => letter,
+            None => return Ok(None),
+        };
+
+        let kind = match letter {
+            '[' => self.read_tag()?,
+            '{' => self.read_comment()?,
+            ';' => {
+                let comment: String = self.buffer[self.index..].iter().collect();
+                self.index = self.buffer.len();
+                TokenKind::Comment(comment.trim().to_string())
+            },
+            '(' => TokenKind::VariationStart,
+            ')' => TokenKind::VariationEnd,
+            '*' => TokenKind::GameResult("*".to_string()),
+            '$' => {
+                let digits = self.read_while(|x| x.is_ascii_digit())?;
+                TokenKind::Nag(digits.parse::<u8>().map_err(|_| PgnError::new(line, column, "Invalid NAG".to_string()))?)
+            },
+            '!' | '?' => {
+                let suffix = format!("{}{}", letter, self.read_while(|x| x == '!' || x == '?')?);
+                TokenKind::Nag(suffix_to_nag(&suffix).ok_or(PgnError::new(line, column, format!("Invalid annotation '{}'", suffix)))?)
+            },
+            letter if is_symbol_char(letter) => {
+                let symbol = format!("{}{}", letter, self.read_while(is_symbol_char)?);
+                match symbol.as_str() {
+                    "1-0" | "0-1" | "1/2-1/2" => TokenKind::GameResult(symbol),
+                    _ if symbol.chars().all(|x| x.is_ascii_digit()) => TokenKind::MoveNumber,
+                    _ => TokenKind::San(symbol),
+                }
+            },
+            letter => return Err(PgnError::new(line, column, format!("Unexpected character '{}'", letter))),
+        };
+
+        Ok(Some(Token { kind, line, column }))
+    }
+}
+
+// Streams games from PGN input one at a time, replaying their moves.
+// A game with an illegal move is reported as an error and skipped.
+pub struct PgnReader<R: BufRead> {
+    lexer: Lexer<R>,
+    peeked: Option<Token>,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> PgnReader<R> {
+        PgnReader { lexer: Lexer::new(reader), peeked: None }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, PgnError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn initial_state(tags: &[(String, String)], fen_token: Option<&Token>) -> Result<GameState, PgnError> {
+        let tag = |tag: &str| tags.iter().find(|(name, _)| name == tag).map(|(_, value)| value.as_str());
+        let mut game_state = GameState::new();
+
+        parse(tag("FEN").unwrap_or(INITIAL_FEN), &mut game_state).map_err(|err| {
+            let (line, column) = fen_token.map_or((0, 0), |token| (token.line, token.column));
+            PgnError::new(line, column, format!("Invalid FEN tag: {}", err))
+        })?;
+
+        if let Some(variant) = tag("Variant").and_then(Variant::from_name) {
+            game_state.variant = variant;
+        }
+
+        Ok(game_state)
+    }
+
+    fn read_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        let mut tags: Vec<(String, String)> = Vec::new();
+        let mut fen_token: Option<Token> = None;
+        let mut leading_comments: Vec<String> = Vec::new();
+
+        loop {
+            match self.next_token()? {
+                Some(Token { kind: TokenKind::Tag(name, value), line, column }) => {
+                    if name == "FEN" {
+                        fen_token = Some(Token { kind: TokenKind::Tag(name.clone(), value.clone()), line, column });
+                    }
+                    tags.push((name, value));
+                },
+                // Comments trailing the last game don't start a new one
+                Some(Token { kind: TokenKind::Comment(comment), .. }) if tags.is_empty() => leading_comments.push(comment),
+                Some(token) => {
+                    self.peeked = Some(token);
+                    break;
+                },
+                None if tags.is_empty() => return Ok(None),
+                None => break,
+            }
+        }
+
+        let initial_state = Self::initial_state(&tags, fen_token.as_ref())?;
+        let mut game_state = initial_state.clone();
+        let mut result: Option<String> = None;
+        let mut moves = self.read_line(&mut game_state, false, &mut result)?;
+        if let Some(first_move) = moves.first_mut() {
+            first_move.comments_before.splice(0..0, leading_comments);
+        }
+
+        Ok(Some(PgnGame { tags, moves, result, initial_state, game_state }))
+    }
+
+    // Reads moves until the end of the game or, inside a variation, until the closing parenthesis
+    fn read_line(&mut self, game_state: &mut GameState, in_variation: bool, result: &mut Option<String>) -> Result<Vec<PgnMove>, PgnError> {
+        let mut moves: Vec<PgnMove> = Vec::new();
+        let mut pending_comments: Vec<String> = Vec::new();
+
+        loop {
+            let token = match self.next_token()? {
+                Some(token) => token,
+                None if in_variation => return Err(self.lexer.error("Unterminated variation")),
+                None => break,
+            };
+            let error = |message: String| PgnError::new(token.line, token.column, message);
+
+            match token.kind {
+                TokenKind::Tag(_, _) if in_variation => return Err(error("Unexpected tag inside a variation".to_string())),
+                TokenKind::Tag(_, _) => {
+                    // Game without a result, the tag belongs to the next one
+                    self.peeked = Some(token);
+                    break;
+                },
+                TokenKind::Comment(comment) => match moves.last_mut() {
+                    Some(last_move) => last_move.comments.push(comment),
+                    None => pending_comments.push(comment),
+                },
+                TokenKind::Nag(nag) => match moves.last_mut() {
+                    Some(last_move) => last_move.nags.push(nag),
+                    None => return Err(error("Annotation without a move".to_string())),
+                },
+                TokenKind::MoveNumber => {},
+                TokenKind::VariationStart => {
+                    if moves.is_empty() {
+                        return Err(error("Variation without a preceding move".to_string()));
+                    }
+
+                    // Variation replaces the last move, so it starts from the position before it
+                    let mut variation_state = game_state.clone();
+                    variation_state.unmake_move().map_err(|err| error(err.to_string()))?;
+                    let variation = self.read_line(&mut variation_state, true, &mut None)?;
+
+                    if let Some(last_move) = moves.last_mut() {
+                        last_move.variations.push(variation);
+                    }
+                },
+                TokenKind::VariationEnd if in_variation => break,
+                TokenKind::VariationEnd => return Err(error("Unmatched ')'".to_string())),
+                TokenKind::GameResult(_) if in_variation => return Err(error("Game result inside a variation".to_string())),
+                TokenKind::GameResult(game_result) => {
+                    *result = Some(game_result);
+                    break;
+                },
+                TokenKind::San(san) => {
+                    let chess_move = parse_san(game_state, &san).map_err(|err| error(err.to_string()))?;
+                    game_state.play_move(&chess_move).map_err(|err| error(format!("{} '{}'", err, san)))?;
+
+                    moves.push(PgnMove {
+                        san,
+                        chess_move,
+                        nags: Vec::new(),
+                        comments_before: mem::take(&mut pending_comments),
+                        comments: Vec::new(),
+                        variations: Vec::new(),
+                    });
+                },
+            }
+        }
+
+        Ok(moves)
+    }
+
+    // Drops the rest of a game after an error, up to its result or the next game's tags
+    fn skip_game(&mut self) {
+        loop {
+            match self.next_token() {
+                Ok(Some(Token { kind: TokenKind::GameResult(_), .. })) | Ok(None) => return,
+                Ok(Some(token @ Token { kind: TokenKind::Tag(_, _), .. })) => {
+                    self.peeked = Some(token);
+                    return;
+                },
+                Err(_) if self.lexer.eof => return,
+                Ok(Some(_)) | Err(_) => {},
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Result<PgnGame, PgnError>> {
+        match self.read_game() {
+            Ok(game) => game.map(Ok),
+            Err(err) => {
+                self.skip_game();
+                Some(Err(err))
+            },
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use super::parser::INITIAL_FEN;
+use super::san::to_san;
+use super::structs::enums::{Color, GameResult, Variant};
+use super::structs::game_state::GameState;
+use super::structs::pgn_metadata::PgnMetadata;
+
+// Export format keeps lines within 79 characters so they fit in 80 columns
+const MAX_LINE_LENGTH: usize = 79;
+
+pub fn result_token(result: Option<&GameResult>) -> &'static str {
+    match result {
+        Some(GameResult::WhiteWin(_)) => "1-0",
+        Some(GameResult::BlackWin(_)) => "0-1",
+        Some(GameResult::Stalement) | Some(GameResult::Draw(_)) => "1/2-1/2",
+        None => "*",
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn write_tag(output: &mut String, name: &str, value: &str) {
+    output.push_str(&format!("[{} \"{}\"]\n", name, escape(value)));
+}
+
+// Joins the tokens with spaces, starting a new line when the next one wouldn't fit
+pub fn wrap_movetext(tokens: &[String]) -> String {
+    let mut output = String::new();
+    let mut line_length = 0;
+
+    for token in tokens {
+        if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+            output.push('\n');
+            line_length = 0;
+        } else if line_length > 0 {
+            output.push(' ');
+            line_length += 1;
+        }
+
+        output.push_str(token);
+        line_length += token.len();
+    }
+
+    output.push('\n');
+    output
+}
+
+// Castling from squares other than the standard ones only happens in Chess960
+fn is_chess960(game_state: &GameState) -> bool {
+    let castles = &game_state.castles;
+    let king_moved = [Color::White, Color::Black].into_iter().any(|color| {
+        let can_castle = castles.can_castle_king_side(color) || castles.can_castle_queen_side(color);
+        can_castle && game_state.find_king(color).is_some_and(|king| king.1 != 4)
+    });
+
+    !castles.is_standard() || king_moved
+}
+
+// Writes the game played so far in PGN export format
+pub fn write_pgn(game_state: &GameState, metadata: &PgnMetadata) -> String {
+    let mut replay = game_state.clone();
+    while replay.unmake_move().is_ok() {}
+
+    let result = result_token(game_state.result.as_ref());
+    let mut output = String::new();
+
+    write_tag(&mut output, "Event", &metadata.event);
+    write_tag(&mut output, "Site", &metadata.site);
+    write_tag(&mut output, "Date", &metadata.date);
+    write_tag(&mut output, "Round", &metadata.round);
+    write_tag(&mut output, "White", &metadata.white);
+    write_tag(&mut output, "Black", &metadata.black);
+    write_tag(&mut output, "Result", result);
+
+    if game_state.variant != Variant::Standard {
+        write_tag(&mut output, "Variant", &game_state.variant.to_string());
+    } else if is_chess960(&replay) {
+        write_tag(&mut output, "Variant", "Chess960");
+    }
+
+    let initial_fen = replay.to_fen();
+    if initial_fen != INITIAL_FEN {
+        write_tag(&mut output, "SetUp", "1");
+        write_tag(&mut output, "FEN", &initial_fen);
+    }
+
+    let termination = match (&metadata.termination, &game_state.result) {
+        (Some(termination), _) => termination.as_str(),
+        (None, Some(_)) => "normal",
+        (None, None) => "unterminated",
+    };
+    write_tag(&mut output, "Termination", termination);
+    output.push('\n');
+
+    let mut tokens: Vec<String> = Vec::new();
+    for (index, entry) in game_state.history.iter().enumerate() {
+        let chess_move = &entry.chess_move;
+        if chess_move.piece.color == Color::White {
+            tokens.push(format!("{}.", replay.full_moves));
+        } else if index == 0 {
+            tokens.push(format!("{}...", replay.full_moves));
+        }
+
+        tokens.push(to_san(&replay, chess_move));
+        replay.make_move(chess_move);
+    }
+
+    // Reason of the ending, e.g. the kind of draw, isn't part of the result token
+    if let Some(game_result) = &game_state.result {
+        tokens.extend(format!("{{{}}}", game_result).split(' ').map(str::to_string));
+    }
+    tokens.push(result.to_string());
+
+    output.push_str(&wrap_movetext(&tokens));
+    output
+}
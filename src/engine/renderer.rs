@@ -1,5 +1,6 @@
-use super::structs::game_state::GameState;
-use super::structs::enums::Color;
+use super::structs::game_state::{GameState, HILL};
+use super::bitboard::bit;
+use super::structs::enums::{Color, Variant};
 use colored::Colorize;
 
 #[macro_export]
@@ -18,30 +19,45 @@ pub fn render_board (game_state: &mut GameState) {
     for i in 0..8 {
         print!( "|{}| |", 8 - i);
         for j in 0..8 {
-            match game_state.board.get(&(i, j)) {
+            let value = match game_state.board.get(&(i, j)) {
                 Some(piece) => {
                     let value = Colorize::bold(&format!(" {} ", piece)[..]);
-                    let value = if piece.color == Color::White { Colorize::green(value) } else { Colorize::red(value) };
-                    if (i + j) % 2 == 1 {
-                        print!("{}|", Colorize::on_black(value));
-                    } else {
-                        print!("{}|", Colorize::on_bright_black(value));
-                    }
+                    if piece.color == Color::White { Colorize::green(value) } else { Colorize::red(value) }
                 },
-                None => {
-                    if (i + j) % 2 == 1 {
-                        print!("{}|", Colorize::on_black("   "));
-                    } else {
-                        print!("{}|", Colorize::on_bright_black("   "));
-                    }
-                },
-            }
+                None => Colorize::normal("   "),
+            };
+
+            // King of the Hill center squares are shown in blue
+            let is_hill = game_state.variant == Variant::KingOfTheHill && HILL & bit((i, j)) != 0;
+            let value = match ((i + j) % 2 == 1, is_hill) {
+                (true, false) => Colorize::on_black(value),
+                (false, false) => Colorize::on_bright_black(value),
+                (true, true) => Colorize::on_blue(value),
+                (false, true) => Colorize::on_bright_blue(value),
+            };
+            print!("{}|", value);
         }
         print!( " |{}|", 8 - i);
-        println!("");
+
+        // Crazyhouse pockets are shown beside each side's back rank
+        if game_state.variant == Variant::Crazyhouse && (i == 0 || i == 7) {
+            let color = if i == 0 { Color::Black } else { Color::White };
+            print!("  Pocket: {}", game_state.pockets[color as usize].to_fen_str(color));
+        }
+
+        // Three-check counters beside each side's back rank as well
+        if game_state.variant == Variant::ThreeCheck && (i == 0 || i == 7) {
+            let color = if i == 0 { Color::Black } else { Color::White };
+            print!("  Checks given: {}/3", game_state.checks[color as usize]);
+        }
+        println!();
         println!("+-+-+---+---+---+---+---+---+---+---+-+-+");
     }
     println!("+-+-+---+---+---+---+---+---+---+---+-+-+");
     println!("| | | A | B | C | D | E | F | G | H | | |");
     println!("+-+-+---+---+---+---+---+---+---+---+-+-+");
+
+    if let Some(result) = &game_state.result {
+        println!("{}", result);
+    }
 }
@@ -0,0 +1,185 @@
+use super::structs::chess_move::{Move, MoveKind};
+use super::structs::enums::{PieceType, Variant};
+use super::structs::game_state::GameState;
+use super::structs::san_error::SanError;
+use super::utils::{couple_to_std_pos, std_pos_to_couple};
+
+fn piece_type_from_letter(letter: char) -> Option<PieceType> {
+    match letter {
+        'K' => Some(PieceType::King),
+        'Q' => Some(PieceType::Queen),
+        'R' => Some(PieceType::Rook),
+        'B' => Some(PieceType::Bishop),
+        'N' => Some(PieceType::Knight),
+        _ => None,
+    }
+}
+
+fn select_move(san: &str, candidates: Vec<Move>) -> Result<Move, SanError> {
+    match candidates.len() {
+        0 => Err(SanError::Illegal(san.to_string())),
+        1 => Ok(candidates[0].clone()),
+        _ => Err(SanError::Ambiguous { san: san.to_string(), candidates }),
+    }
+}
+
+// Resolves a move in Standard Algebraic Notation, e.g. Nf3, exd5, Nbd7, R1e2, O-O, e8=Q+ or N@f3,
+// against the legal moves of the position
+pub fn parse_san(game_state: &GameState, input: &str) -> Result<Move, SanError> {
+    let san = input.trim().trim_end_matches(['+', '#', '!', '?']);
+    let invalid = || SanError::Invalid(input.trim().to_string());
+    let legal_moves = game_state.legal_moves();
+
+    // Crazyhouse drop, e.g. N@f3, with pawns written as P@e4 or @e4
+    if let Some((letter, square)) = san.split_once('@') {
+        let piece_type = match letter {
+            "" | "P" => PieceType::Pawn,
+            _ if letter.len() == 1 => letter.chars().next().and_then(piece_type_from_letter).ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+        let to = std_pos_to_couple(square.to_string()).map_err(|_| invalid())?;
+
+        return select_move(san, legal_moves.into_iter().filter(|x| x.is_drop() && x.piece.piece_type == piece_type && x.to == to).collect());
+    }
+
+    let castle_kind = match san {
+        "O-O" | "0-0" => Some(MoveKind::KingSideCastle),
+        "O-O-O" | "0-0-0" => Some(MoveKind::QueenSideCastle),
+        _ => None,
+    };
+    if let Some(kind) = castle_kind {
+        return select_move(san, legal_moves.into_iter().filter(|x| x.kind == kind).collect());
+    }
+
+    let mut chars: Vec<char> = san.chars().collect();
+
+    // Promotion suffix, both e8=Q and e8Q
+    let promotion = match chars.last().copied().and_then(piece_type_from_letter) {
+        Some(piece_type) => {
+            chars.pop();
+            if chars.last() == Some(&'=') {
+                chars.pop();
+            }
+            Some(piece_type)
+        },
+        None => None,
+    };
+
+    if chars.len() < 2 {
+        return Err(invalid());
+    }
+
+    let destination: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to = std_pos_to_couple(destination).map_err(|_| invalid())?;
+
+    let piece_type = match chars.first().copied().and_then(piece_type_from_letter) {
+        Some(piece_type) => {
+            chars.remove(0);
+            piece_type
+        },
+        None => PieceType::Pawn,
+    };
+
+    if chars.last() == Some(&'x') {
+        chars.pop();
+    }
+
+    // Whatever is left disambiguates the source square by file, rank or both
+    let mut from_column: Option<i8> = None;
+    let mut from_row: Option<i8> = None;
+    for letter in chars {
+        match letter {
+            'a'..='h' if from_column.is_none() && from_row.is_none() => { from_column = Some(letter as i8 - 'a' as i8) },
+            '1'..='8' if from_row.is_none() => { from_row = Some('8' as i8 - letter as i8) },
+            _ => return Err(invalid()),
+        }
+    }
+
+    let candidates: Vec<Move> = legal_moves
+        .into_iter()
+        .filter(|x| x.piece.piece_type == piece_type && x.to == to)
+        .filter(|x| !x.is_castle() && !x.is_drop())
+        .filter(|x| from_column.is_none_or(|column| x.from.1 == column))
+        .filter(|x| from_row.is_none_or(|row| x.from.0 == row))
+        .collect();
+
+    // Pawn reaching the last rank must name the piece it promotes to
+    if promotion.is_none() && candidates.iter().any(Move::is_promotion) {
+        return Err(SanError::MissingPromotion(san.to_string()));
+    }
+
+    select_move(san, candidates.into_iter().filter(|x| x.promotion == promotion).collect())
+}
+
+fn piece_letter(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::King => "K",
+        PieceType::Queen => "Q",
+        PieceType::Rook => "R",
+        PieceType::Bishop => "B",
+        PieceType::Knight => "N",
+        PieceType::Pawn => "",
+    }
+}
+
+// Writes the legal move in minimally disambiguated Standard Algebraic Notation
+pub fn to_san(game_state: &GameState, chess_move: &Move) -> String {
+    let mut san = match chess_move.kind {
+        MoveKind::KingSideCastle => "O-O".to_string(),
+        MoveKind::QueenSideCastle => "O-O-O".to_string(),
+        MoveKind::Drop => {
+            let letter = if chess_move.piece.piece_type == PieceType::Pawn { "P" } else { piece_letter(chess_move.piece.piece_type) };
+            format!("{}@{}", letter, couple_to_std_pos(chess_move.to))
+        },
+        _ => {
+            let mut san = piece_letter(chess_move.piece.piece_type).to_string();
+            let from = couple_to_std_pos(chess_move.from);
+
+            if chess_move.piece.piece_type == PieceType::Pawn {
+                if chess_move.is_capture() {
+                    san.push_str(&from[..1]);
+                }
+            } else {
+                let others: Vec<Move> = game_state
+                    .legal_moves()
+                    .into_iter()
+                    .filter(|x| x.piece == chess_move.piece && x.to == chess_move.to && x.from != chess_move.from && !x.is_drop())
+                    .collect();
+
+                if !others.is_empty() {
+                    if others.iter().all(|x| x.from.1 != chess_move.from.1) {
+                        san.push_str(&from[..1]);
+                    } else if others.iter().all(|x| x.from.0 != chess_move.from.0) {
+                        san.push_str(&from[1..]);
+                    } else {
+                        san.push_str(&from);
+                    }
+                }
+            }
+
+            if chess_move.is_capture() {
+                san.push('x');
+            }
+
+            san.push_str(&couple_to_std_pos(chess_move.to));
+
+            if let Some(promotion) = chess_move.promotion {
+                san.push('=');
+                san.push_str(piece_letter(promotion));
+            }
+
+            san
+        },
+    };
+
+    let mut next_state = game_state.clone();
+    next_state.make_move(chess_move);
+    // Blowing up the king in Atomic ends the game like a mate
+    if next_state.variant == Variant::Atomic && next_state.find_king(next_state.whose_move).is_none() {
+        san.push('#');
+    } else if next_state.is_in_check(next_state.whose_move) {
+        san.push(if next_state.legal_moves().is_empty() { '#' } else { '+' });
+    }
+
+    san
+}
@@ -1,6 +1,95 @@
-use super::enums::Position;
+use super::enums::{Color, Position, PieceType};
 use super::piece::Piece;
+use crate::engine::bitboard::{Bitboard, bit, positions, position_of};
 
-use std::collections::HashMap;
+const PIECE_TYPES: [PieceType; 6] = [
+    PieceType::Pawn,
+    PieceType::King,
+    PieceType::Queen,
+    PieceType::Knight,
+    PieceType::Rook,
+    PieceType::Bishop,
+];
 
-pub type BoardMap = HashMap<Position, Piece>;
+// Board stored as one bitboard per piece type and one per color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BoardMap {
+    piece_types: [Bitboard; 6],
+    colors: [Bitboard; 2],
+}
+
+impl BoardMap {
+    pub fn new() -> BoardMap {
+        BoardMap { piece_types: [0; 6], colors: [0; 2] }
+    }
+
+    pub fn get(&self, position: &Position) -> Option<Piece> {
+        let mask = bit(*position);
+        if self.occupied() & mask == 0 {
+            return None;
+        }
+
+        let color = if self.colors[Color::White as usize] & mask != 0 { Color::White } else { Color::Black };
+        PIECE_TYPES
+            .iter()
+            .find(|piece_type| self.piece_types[**piece_type as usize] & mask != 0)
+            .map(|piece_type| Piece::new(*piece_type, color))
+    }
+
+    pub fn contains_key(&self, position: &Position) -> bool {
+        self.occupied() & bit(*position) != 0
+    }
+
+    pub fn insert(&mut self, position: Position, piece: Piece) -> Option<Piece> {
+        let old_piece = self.remove(&position);
+        let mask = bit(position);
+        self.piece_types[piece.piece_type as usize] |= mask;
+        self.colors[piece.color as usize] |= mask;
+        old_piece
+    }
+
+    pub fn remove(&mut self, position: &Position) -> Option<Piece> {
+        let piece = self.get(position)?;
+        let mask = !bit(*position);
+        self.piece_types[piece.piece_type as usize] &= mask;
+        self.colors[piece.color as usize] &= mask;
+        Some(piece)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, Piece)> + '_ {
+        positions(self.occupied()).filter_map(|position| self.get(&position).map(|piece| (position, piece)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.occupied().count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.occupied() == 0
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+
+    pub fn by_color(&self, color: Color) -> Bitboard {
+        self.colors[color as usize]
+    }
+
+    pub fn by_type(&self, piece_type: PieceType) -> Bitboard {
+        self.piece_types[piece_type as usize]
+    }
+
+    pub fn pieces(&self, piece_type: PieceType, color: Color) -> Bitboard {
+        self.piece_types[piece_type as usize] & self.colors[color as usize]
+    }
+
+    pub fn king_square(&self, color: Color) -> Option<usize> {
+        let kings = self.pieces(PieceType::King, color);
+        if kings == 0 { None } else { Some(kings.trailing_zeros() as usize) }
+    }
+
+    pub fn piece_at(&self, square: usize) -> Option<Piece> {
+        self.get(&position_of(square))
+    }
+}
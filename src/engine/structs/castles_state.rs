@@ -1,34 +1,137 @@
+use super::board_map::BoardMap;
+use super::enums::{Color, Position, PieceType};
+use super::piece::Piece;
+
+// Castling rights, each holding the starting column of the rook it castles with.
+// Standard chess uses columns 7 and 0, Chess960 any column on the king's side.
 #[derive(Debug, Clone)]
 pub struct CastlesState {
-    pub white_king: bool,
-    pub white_queen: bool,
-    pub black_king: bool,
-    pub black_queen: bool,
+    pub white_king: Option<i8>,
+    pub white_queen: Option<i8>,
+    pub black_king: Option<i8>,
+    pub black_queen: Option<i8>,
 }
 
 impl CastlesState {
     pub fn new() -> CastlesState {
         CastlesState {
-            white_king: false,
-            white_queen: false,
-            black_king: false,
-            black_queen: false,
+            white_king: None,
+            white_queen: None,
+            black_king: None,
+            black_queen: None,
         }
     }
 
-    pub fn from_fen_str(fen_str: String) -> CastlesState {
+    // Reads X-FEN (KQkq, meaning the outermost rook on that side of the king)
+    // as well as Shredder-FEN (rook files, e.g. HAha). Expects valid characters.
+    pub fn from_fen_str(fen_str: &str, board: &BoardMap) -> CastlesState {
         let mut castles = CastlesState::new();
 
-        for i in fen_str.chars() {
-            match i {
-                'K' => { castles.white_king = true },
-                'Q' => { castles.white_queen = true },
-                'k' => { castles.black_king = true },
-                'q' => { castles.black_queen = true },
-                _ => {},
+        for letter in fen_str.chars() {
+            let color = if letter.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let row = if color == Color::White { 7 } else { 0 };
+            let rook = Piece::new(PieceType::Rook, color);
+            let king_column = (0..8).find(|x| board.get(&(row, *x)) == Some(Piece::new(PieceType::King, color))).unwrap_or(4);
+
+            let (king_side, column) = match letter.to_ascii_lowercase() {
+                'k' => (true, (king_column + 1..8).rev().find(|x| board.get(&(row, *x)) == Some(rook)).unwrap_or(7)),
+                'q' => (false, (0..king_column).find(|x| board.get(&(row, *x)) == Some(rook)).unwrap_or(0)),
+                file => {
+                    let column = file as i8 - 'a' as i8;
+                    (column > king_column, column)
+                },
+            };
+
+            match (color, king_side) {
+                (Color::White, true) => { castles.white_king = Some(column) },
+                (Color::White, false) => { castles.white_queen = Some(column) },
+                (Color::Black, true) => { castles.black_king = Some(column) },
+                (Color::Black, false) => { castles.black_queen = Some(column) },
             }
         }
 
         castles
     }
+
+    // X-FEN KQkq when every castling rook stands on the a or h file, as those are
+    // always the outermost ones. Otherwise Shredder-FEN files for all the rights,
+    // since other tools don't read a mix of both notations alike.
+    pub fn to_fen_str(&self) -> String {
+        let rights = [
+            (self.white_king, 7, 'K'),
+            (self.white_queen, 0, 'Q'),
+            (self.black_king, 7, 'k'),
+            (self.black_queen, 0, 'q'),
+        ];
+
+        let is_standard = self.is_standard();
+        let fen_str: String = rights
+            .iter()
+            .filter_map(|(right, _, letter)| {
+                let file = (b'a' + (*right)? as u8) as char;
+                Some(match (is_standard, letter.is_ascii_uppercase()) {
+                    (true, _) => *letter,
+                    (false, true) => file.to_ascii_uppercase(),
+                    (false, false) => file,
+                })
+            })
+            .collect();
+
+        if fen_str.is_empty() { "-".to_string() } else { fen_str }
+    }
+
+    // Whether every castling rook starts on the a or h file, as in standard chess
+    pub fn is_standard(&self) -> bool {
+        [self.white_king, self.black_king].iter().all(|x| x.is_none_or(|column| column == 7))
+            && [self.white_queen, self.black_queen].iter().all(|x| x.is_none_or(|column| column == 0))
+    }
+
+    pub fn king_side_rook(&self, color: Color) -> Option<i8> {
+        if color == Color::White { self.white_king } else { self.black_king }
+    }
+
+    pub fn queen_side_rook(&self, color: Color) -> Option<i8> {
+        if color == Color::White { self.white_queen } else { self.black_queen }
+    }
+
+    pub fn can_castle_king_side(&self, color: Color) -> bool {
+        self.king_side_rook(color).is_some()
+    }
+
+    pub fn can_castle_queen_side(&self, color: Color) -> bool {
+        self.queen_side_rook(color).is_some()
+    }
+
+    // Revokes both rights of the color, i.e. after a king move
+    pub fn revoke(&mut self, color: Color) {
+        if color == Color::White {
+            self.white_king = None;
+            self.white_queen = None;
+        } else {
+            self.black_king = None;
+            self.black_queen = None;
+        }
+    }
+
+    // Revokes rights whose rook leaves or gets captured on the square
+    pub fn revoke_by_square(&mut self, position: Position) {
+        let column = Some(position.1);
+        match position.0 {
+            7 => {
+                if self.white_king == column { self.white_king = None }
+                if self.white_queen == column { self.white_queen = None }
+            },
+            0 => {
+                if self.black_king == column { self.black_king = None }
+                if self.black_queen == column { self.black_queen = None }
+            },
+            _ => {},
+        }
+    }
+}
+
+impl Default for CastlesState {
+    fn default() -> CastlesState {
+        CastlesState::new()
+    }
 }
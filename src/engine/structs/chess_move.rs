@@ -0,0 +1,91 @@
+use super::enums::{Position, PieceType};
+use super::piece::Piece;
+use crate::engine::uci::to_uci;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveKind {
+    Normal,
+    DoublePush,
+    EnPasant,
+    KingSideCastle,
+    QueenSideCastle,
+    // Crazyhouse piece placed from the pocket, from and to are both the target square
+    Drop,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Move {
+    pub from: Position,
+    pub to: Position,
+    pub piece: Piece,
+    pub captured: Option<Piece>,
+    pub promotion: Option<PieceType>,
+    pub kind: MoveKind,
+    // Starting square of the rook for castling moves, any file in Chess960
+    pub castle_rook: Option<Position>,
+}
+
+impl Move {
+    pub fn new(from: Position, to: Position, piece: Piece) -> Move {
+        Move { from, to, piece, captured: None, promotion: None, kind: MoveKind::Normal, castle_rook: None }
+    }
+
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    pub fn is_castle(&self) -> bool {
+        self.kind == MoveKind::KingSideCastle || self.kind == MoveKind::QueenSideCastle
+    }
+
+    pub fn is_en_pasant(&self) -> bool {
+        self.kind == MoveKind::EnPasant
+    }
+
+    pub fn is_drop(&self) -> bool {
+        self.kind == MoveKind::Drop
+    }
+
+    pub fn is_promotion(&self) -> bool {
+        self.promotion.is_some()
+    }
+
+    // Square of the captured piece, differs from destination for en pasant
+    pub fn capture_position(&self) -> Option<Position> {
+        match self.captured {
+            Some(_) if self.is_en_pasant() => Some((self.from.0, self.to.1)),
+            Some(_) => Some(self.to),
+            None => None,
+        }
+    }
+
+    // Rook's source and destination squares for a castling move, the rook
+    // always ends next to the king on the f or d file
+    pub fn castle_rook_positions(&self) -> Option<(Position, Position)> {
+        let rook = self.castle_rook?;
+        match self.kind {
+            MoveKind::KingSideCastle => Some((rook, (rook.0, 5))),
+            MoveKind::QueenSideCastle => Some((rook, (rook.0, 3))),
+            _ => None,
+        }
+    }
+
+    // Chess960 castling can't be told apart by the king's destination, so
+    // unless king and rook start on standard squares it's written as the king
+    // moving onto its rook
+    pub fn is_standard_castle(&self) -> bool {
+        match self.kind {
+            MoveKind::KingSideCastle => self.from.1 == 4 && self.castle_rook.map(|x| x.1) == Some(7),
+            MoveKind::QueenSideCastle => self.from.1 == 4 && self.castle_rook.map(|x| x.1) == Some(0),
+            _ => false,
+        }
+    }
+}
+
+// UCI long algebraic notation, e.g. e2e4 or e7e8q
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", to_uci(self))
+    }
+}
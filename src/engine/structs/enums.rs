@@ -1,10 +1,18 @@
-#[derive(Debug, PartialEq, Clone, Copy)]
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Color {
     Black,
     White,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl Color {
+    pub fn opposite(self) -> Color {
+        if self == Color::White { Color::Black } else { Color::White }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PieceType {
     Pawn,
     King,
@@ -14,11 +22,118 @@ pub enum PieceType {
     Bishop,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawReason {
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    InsufficientMaterial,
+    BothKingsReachedGoal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WinReason {
+    Checkmate,
+    KingExploded,
+    ThirdCheck,
+    KingOfTheHill,
+    AllPiecesLost,
+    Stalemated,
+    AllPiecesCaptured,
+    KingReachedGoal,
+}
+
 #[derive(Debug, Clone)]
 pub enum GameResult {
-    WhiteWin,
-    BlackWin,
+    WhiteWin(WinReason),
+    BlackWin(WinReason),
     Stalement,
+    Draw(DrawReason),
+}
+
+impl GameResult {
+    pub fn win(color: Color, reason: WinReason) -> GameResult {
+        if color == Color::White { GameResult::WhiteWin(reason) } else { GameResult::BlackWin(reason) }
+    }
+}
+
+pub type Position = (i8, i8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    #[default]
+    Standard,
+    Crazyhouse,
+    Atomic,
+    ThreeCheck,
+    KingOfTheHill,
+    Antichess,
+    Horde,
+    RacingKings,
+}
+
+impl Variant {
+    // Reads the name used by the PGN Variant tag
+    pub fn from_name(name: &str) -> Option<Variant> {
+        match name.to_ascii_lowercase().as_str() {
+            "standard" | "chess960" => Some(Variant::Standard),
+            "crazyhouse" => Some(Variant::Crazyhouse),
+            "atomic" => Some(Variant::Atomic),
+            "three-check" | "threecheck" | "3check" => Some(Variant::ThreeCheck),
+            "king of the hill" | "kingofthehill" | "koth" => Some(Variant::KingOfTheHill),
+            "antichess" | "giveaway" | "losing chess" => Some(Variant::Antichess),
+            "horde" => Some(Variant::Horde),
+            "racing kings" | "racingkings" => Some(Variant::RacingKings),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Standard => write!(f, "Standard"),
+            Variant::Crazyhouse => write!(f, "Crazyhouse"),
+            Variant::Atomic => write!(f, "Atomic"),
+            Variant::ThreeCheck => write!(f, "Three-check"),
+            Variant::KingOfTheHill => write!(f, "King of the Hill"),
+            Variant::Antichess => write!(f, "Antichess"),
+            Variant::Horde => write!(f, "Horde"),
+            Variant::RacingKings => write!(f, "Racing Kings"),
+        }
+    }
+}
+
+impl fmt::Display for WinReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WinReason::Checkmate => write!(f, "by checkmate"),
+            WinReason::KingExploded => write!(f, "by exploding the king"),
+            WinReason::ThirdCheck => write!(f, "by the third check"),
+            WinReason::KingOfTheHill => write!(f, "by reaching the hill"),
+            WinReason::AllPiecesLost => write!(f, "by losing all pieces"),
+            WinReason::Stalemated => write!(f, "by being stalemated"),
+            WinReason::AllPiecesCaptured => write!(f, "by capturing all pieces"),
+            WinReason::KingReachedGoal => write!(f, "by reaching the eighth rank"),
+        }
+    }
 }
 
-pub type Position = (i8, i8);
\ No newline at end of file
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameResult::WhiteWin(reason) => write!(f, "White wins {}", reason),
+            GameResult::BlackWin(reason) => write!(f, "Black wins {}", reason),
+            GameResult::Stalement => write!(f, "Draw by stalemate"),
+            GameResult::Draw(reason) => match reason {
+                DrawReason::FiftyMoveRule => write!(f, "Draw by fifty-move rule"),
+                DrawReason::SeventyFiveMoveRule => write!(f, "Draw by seventy-five-move rule"),
+                DrawReason::ThreefoldRepetition => write!(f, "Draw by threefold repetition"),
+                DrawReason::FivefoldRepetition => write!(f, "Draw by fivefold repetition"),
+                DrawReason::InsufficientMaterial => write!(f, "Draw by insufficient material"),
+                DrawReason::BothKingsReachedGoal => write!(f, "Draw by both kings reaching the eighth rank"),
+            },
+        }
+    }
+}
\ No newline at end of file
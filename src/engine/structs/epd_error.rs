@@ -0,0 +1,20 @@
+use super::fen_error::FenError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EpdError {
+    Fen(FenError),
+    // Operation that doesn't start with an opcode or has an unterminated string
+    InvalidOperation(String),
+    InvalidOperand { opcode: String, operand: String },
+}
+
+impl fmt::Display for EpdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EpdError::Fen(err) => write!(f, "{}", err),
+            EpdError::InvalidOperation(operation) => write!(f, "Invalid operation '{}'", operation),
+            EpdError::InvalidOperand { opcode, operand } => write!(f, "Invalid operand '{}' of '{}'", operand, opcode),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use super::chess_move::Move;
+use super::game_state::GameState;
+
+// Position with the opcodes we understand already interpreted
+#[derive(Debug, Clone)]
+pub struct EpdRecord {
+    pub game_state: GameState,
+    pub best_moves: Vec<Move>,
+    pub avoid_moves: Vec<Move>,
+    pub id: Option<String>,
+    // Comments c0 to c9
+    pub comments: [Option<String>; 10],
+    pub direct_mate: Option<u32>,
+    pub predicted_variation: Vec<Move>,
+    pub analysis_depth: Option<u32>,
+    // Every operation in order, including the interpreted ones
+    pub operations: Vec<(String, Vec<String>)>,
+}
+
+impl EpdRecord {
+    pub fn new(game_state: GameState) -> EpdRecord {
+        EpdRecord {
+            game_state,
+            best_moves: Vec::new(),
+            avoid_moves: Vec::new(),
+            id: None,
+            comments: Default::default(),
+            direct_mate: None,
+            predicted_variation: Vec::new(),
+            analysis_depth: None,
+            operations: Vec::new(),
+        }
+    }
+
+    // Chosen move solves the position if it's one of the best moves and none of the avoided ones
+    pub fn is_solved_by(&self, chess_move: &Move) -> bool {
+        (self.best_moves.is_empty() || self.best_moves.contains(chess_move)) && !self.avoid_moves.contains(chess_move)
+    }
+}
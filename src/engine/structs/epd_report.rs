@@ -0,0 +1,29 @@
+use super::epd_error::EpdError;
+
+#[derive(Debug, Clone)]
+pub struct EpdTestResult {
+    pub line: usize,
+    pub id: Option<String>,
+    // Moves in SAN, chosen is None when the chooser gave up
+    pub chosen: Option<String>,
+    pub best_moves: Vec<String>,
+    pub avoid_moves: Vec<String>,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EpdReport {
+    pub results: Vec<EpdTestResult>,
+    // Lines that couldn't be parsed
+    pub errors: Vec<(usize, EpdError)>,
+}
+
+impl EpdReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|x| x.passed).count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.results.len()
+    }
+}
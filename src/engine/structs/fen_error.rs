@@ -0,0 +1,61 @@
+use super::enums::{Color, Position};
+use crate::engine::utils::couple_to_std_pos;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FenField {
+    Position,
+    WhoseMove,
+    Castles,
+    EnPasant,
+    HalfMoves,
+    FullMoves,
+    Checks,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenError {
+    // Syntax errors
+    FieldCount(usize),
+    RankCount(usize),
+    // Rank as on the board, 8 to 1, with the number of files it describes
+    RankLength { rank: usize, files: usize },
+    InvalidCharacter { field: FenField, character: char },
+    InvalidValue { field: FenField, value: String },
+    // Semantic errors
+    KingCount { color: Color, count: u32 },
+    PawnOnBackRank(Position),
+    OpponentInCheck,
+    InvalidEnPasant(Position),
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            FenField::Position => "piece placement",
+            FenField::WhoseMove => "side to move",
+            FenField::Castles => "castling availability",
+            FenField::EnPasant => "en pasant square",
+            FenField::HalfMoves => "halfmove clock",
+            FenField::FullMoves => "fullmove number",
+            FenField::Checks => "check counters",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FenError::FieldCount(count) => write!(f, "Expected 6 fields, found {}", count),
+            FenError::RankCount(count) => write!(f, "Expected 8 ranks, found {}", count),
+            FenError::RankLength { rank, files } => write!(f, "Rank {} describes {} files instead of 8", rank, files),
+            FenError::InvalidCharacter { field, character } => write!(f, "Invalid character '{}' in {}", character, field),
+            FenError::InvalidValue { field, value } => write!(f, "Invalid {} '{}'", field, value),
+            FenError::KingCount { color, count } => write!(f, "{:?} has {} kings instead of 1", color, count),
+            FenError::PawnOnBackRank(position) => write!(f, "Pawn on back rank at {}", couple_to_std_pos(*position)),
+            FenError::OpponentInCheck => write!(f, "Side not to move is in check"),
+            FenError::InvalidEnPasant(position) => write!(f, "En pasant square {} is inconsistent with the position", couple_to_std_pos(*position)),
+        }
+    }
+}
@@ -1,13 +1,29 @@
-use crate::engine::move_generator::generate_attacked_fields;
 use std::collections::HashSet;
-use super::enums::{Color, Position, PieceType, GameResult};
+use super::enums::{Color, Position, PieceType, GameResult, DrawReason, Variant, WinReason};
 use super::piece::Piece;
 use super::board_map::BoardMap;
 use super::castles_state::CastlesState;
-use crate::engine::move_generator::generate_valid_destinations;
+use super::pocket::Pocket;
+use crate::engine::move_generator::{
+    generate_valid_destinations_bitboard, generate_moves, generate_all_moves, generate_drops, attackers_bitboard, pins,
+    explosion, is_atomic_check,
+};
+use crate::engine::zobrist::{board_key, side_key, castles_key, en_pasant_key, pockets_key, checks_key};
+use crate::engine::bitboard::{Bitboard, bit, between, position_of, positions, square_of};
+use super::chess_move::{Move, MoveKind};
+use super::history_entry::HistoryEntry;
+use super::pin::Pin;
+use crate::engine::utils::couple_to_std_pos;
+
+// Central squares d4, e4, d5 and e5 a king wins by reaching in King of the Hill
+pub const HILL: Bitboard = 0x0000_0018_1800_0000;
+
+// Eighth rank the kings race to in Racing Kings
+const GOAL: Bitboard = 0xFF00_0000_0000_0000;
 
 #[derive(Debug, Clone)]
 pub struct GameState {
+    pub variant: Variant,
     pub board: BoardMap,
     pub whose_move: Color,
     pub castles: CastlesState,
@@ -15,31 +31,216 @@ pub struct GameState {
     pub half_moves: u16,
     pub full_moves: u16,
     pub result: Option<GameResult>,
+    // Crazyhouse pieces in hand, indexed by color
+    pub pockets: [Pocket; 2],
+    // Squares of pieces that were promoted, they turn back into pawns when captured in Crazyhouse
+    pub promoted: Bitboard,
+    // Checks given by each side in Three-check, indexed by color
+    pub checks: [u8; 2],
+    // Repetition keys of every position preceding the current one
+    pub position_history: Vec<u64>,
+    pub history: Vec<HistoryEntry>,
+    // Undone moves, most recently undone last
+    pub redo_stack: Vec<Move>,
 }
 
 impl GameState {
     pub fn new () -> GameState {
         GameState {
+            variant: Variant::Standard,
             board: BoardMap::new(),
             castles: CastlesState::new(),
             en_pasant_position: None,
             full_moves: 1,
             half_moves: 0,
             whose_move: Color::White,
-            result: None
+            result: None,
+            pockets: [Pocket::new(); 2],
+            promoted: 0,
+            checks: [0; 2],
+            position_history: Vec::new(),
+            history: Vec::new(),
+            redo_stack: Vec::new(),
         }
     }
 
     pub fn find_king(&self, color: Color) -> Option<Position> {
-        self.board.iter().find(|(_, piece)| piece.color == color && piece.piece_type == PieceType::King).map(|(pos, _)| *pos)
+        self.board.king_square(color).map(position_of)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut rows: Vec<String> = Vec::new();
+        for i in 0..8 {
+            let mut row = String::new();
+            let mut empty = 0;
+            for j in 0..8 {
+                match self.board.get(&(i, j)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            row.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        row.push(piece.to_fen_char());
+                        if self.variant == Variant::Crazyhouse && self.promoted & bit((i, j)) != 0 {
+                            row.push('~');
+                        }
+                    },
+                    None => { empty += 1 },
+                }
+            }
+            if empty > 0 {
+                row.push_str(&empty.to_string());
+            }
+            rows.push(row);
+        }
+
+        // Crazyhouse pockets follow the placement, e.g. [QPnn]
+        let mut placement = rows.join("/");
+        if self.variant == Variant::Crazyhouse {
+            placement = format!(
+                "{}[{}{}]",
+                placement,
+                self.pockets[Color::White as usize].to_fen_str(Color::White),
+                self.pockets[Color::Black as usize].to_fen_str(Color::Black),
+            );
+        }
+
+        let whose_move = if self.whose_move == Color::White { "w" } else { "b" };
+        let en_pasant_position = self.en_pasant_position.map_or("-".to_string(), couple_to_std_pos);
+
+        let fen = format!(
+            "{} {} {} {} {} {}",
+            placement,
+            whose_move,
+            self.castles.to_fen_str(),
+            en_pasant_position,
+            self.half_moves,
+            self.full_moves,
+        );
+
+        // Three-check counters of checks given by White and Black
+        if self.variant == Variant::ThreeCheck {
+            return format!("{} +{}+{}", fen, self.checks[Color::White as usize], self.checks[Color::Black as usize]);
+        }
+
+        fen
+    }
+
+    // Pieces of the given color attacking the square
+    pub fn attackers_of(&self, square: Position, color: Color) -> Vec<Position> {
+        positions(attackers_bitboard(&self.board, square_of(square), color, self.board.occupied())).collect()
+    }
+
+    // Whether any piece of the given color attacks the square
+    pub fn is_square_attacked(&self, square: Position, color: Color) -> bool {
+        attackers_bitboard(&self.board, square_of(square), color, self.board.occupied()) != 0
+    }
+
+    pub fn is_in_check(&self, color: Color) -> bool {
+        // King is an ordinary piece in Antichess
+        if self.variant == Variant::Antichess {
+            return false;
+        }
+
+        if self.variant == Variant::Atomic {
+            return is_atomic_check(&self.board, color);
+        }
+
+        self.find_king(color).is_some_and(|king| self.is_square_attacked(king, color.opposite()))
+    }
+
+    // Pieces giving check to the king of the side to move
+    pub fn checkers(&self) -> Vec<Position> {
+        self.find_king(self.whose_move)
+            .map_or_else(Vec::new, |king| self.attackers_of(king, self.whose_move.opposite()))
+    }
+
+    // Pieces of the given color pinned to their own king
+    pub fn pinned_pieces(&self, color: Color) -> Vec<Pin> {
+        let king = match self.board.king_square(color) {
+            Some(king) => king,
+            None => return Vec::new(),
+        };
+
+        pins(&self.board, color)
+            .into_iter()
+            .map(|(pinned, pinner)| Pin {
+                pinned: position_of(pinned),
+                pinner: position_of(pinner),
+                ray: positions(between(king, pinner) | (1 << pinner)).collect(),
+            })
+            .collect()
+    }
+
+    // Position of the pawn captured en pasant by the move, if the move is one
+    pub fn en_pasant_capture(&self, from: Position, to: Position) -> Option<Position> {
+        let piece = self.board.get(&from)?;
+        let is_en_pasant = piece.piece_type == PieceType::Pawn
+            && from.1 != to.1
+            && self.en_pasant_position == Some(to)
+            && !self.board.contains_key(&to);
+
+        if is_en_pasant { Some((from.0, to.1)) } else { None }
     }
 
-    pub fn fake_move(&self, from: Position, to: Position) -> GameState {
-        let mut new_state = self.clone();
-        if let Some(piece) = new_state.board.remove(&from) {
-            new_state.board.insert(to, piece);
+    // Board after moving the piece, without any other state changes
+    pub fn board_after(&self, from: Position, to: Position) -> BoardMap {
+        let mut board = self.board;
+        if let Some(captured) = self.en_pasant_capture(from, to) {
+            board.remove(&captured);
+        }
+        if let Some(piece) = board.remove(&from) {
+            board.insert(to, piece);
         }
-        new_state
+        board
+    }
+
+    // Promotion is mandatory for a pawn reaching the last rank and forbidden otherwise.
+    // The promoted piece always takes the color of the moving pawn.
+    fn validate_promotion(&self, piece: &Piece, to: Position, prom_piece: Option<Piece>) -> Result<Option<PieceType>, &'static str> {
+        let last_row = if piece.color == Color::White { 0 } else { 7 };
+        let is_promotion = piece.piece_type == PieceType::Pawn && to.0 == last_row;
+
+        match prom_piece {
+            None if is_promotion => Err("Promotion piece is required!"),
+            None => Ok(None),
+            Some(_) if !is_promotion => Err("Promotion is only allowed for pawns reaching the last rank!"),
+            Some(prom_piece) => match prom_piece.piece_type {
+                PieceType::Queen | PieceType::Rook | PieceType::Bishop | PieceType::Knight => Ok(Some(prom_piece.piece_type)),
+                PieceType::King if self.variant == Variant::Antichess => Ok(Some(PieceType::King)),
+                _ => Err("Pawn can only be promoted to a queen, rook, bishop or knight!"),
+            },
+        }
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        generate_all_moves(self, self.whose_move)
+    }
+
+    // Resolves coordinates of a move into one of the legal moves
+    pub fn find_move(&self, from: Position, to: Position, prom_piece: Option<Piece>) -> Result<Move, &'static str> {
+        let piece = self.board.get(&from).ok_or("Piece is missing from source square!")?;
+
+        if piece.color != self.whose_move {
+            return Err("Not your turn!");
+        }
+
+        // Chess960 castling is given as the king moving onto its rook
+        let candidates: Vec<Move> = generate_moves(self, piece, from)
+            .into_iter()
+            .filter(|x| match x.castle_rook {
+                Some(rook) if !x.is_standard_castle() => rook == to,
+                _ => x.to == to,
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            return Err("Move is not valid!");
+        }
+
+        let promotion = self.validate_promotion(&piece, to, prom_piece)?;
+
+        candidates.into_iter().find(|x| x.promotion == promotion).ok_or("Move is not valid!")
     }
 
     pub fn execute_move(&mut self, from: Position, to: Position, prom_piece: Option<Piece>) -> Result<(), &'static str> {
@@ -47,77 +248,409 @@ impl GameState {
             return Err("Game is ended!");
         }
 
-        let piece = self.board.get(&from).ok_or("Piece is missing from source square!")?;
+        let chess_move = self.find_move(from, to, prom_piece)?;
+        self.apply_move(&chess_move);
+        self.redo_stack.clear();
 
-        if piece.color != self.whose_move {
+        Ok(())
+    }
+
+    pub fn play_move(&mut self, chess_move: &Move) -> Result<(), &'static str> {
+        if self.result.is_some() {
+            return Err("Game is ended!");
+        }
+
+        if chess_move.piece.color != self.whose_move {
             return Err("Not your turn!");
         }
 
-        let clone_piece = piece.clone();
-        let valid_moves = generate_valid_destinations(self, piece.clone(), from);
+        let valid_moves = if chess_move.is_drop() {
+            generate_drops(self, chess_move.piece.color)
+        } else {
+            generate_moves(self, chess_move.piece, chess_move.from)
+        };
 
-        if !valid_moves.contains(&to) {
+        if !valid_moves.contains(chess_move) {
             return Err("Move is not valid!");
         }
 
-        if self.whose_move != clone_piece.color {
-            return Err("Wrong turn!");
+        self.apply_move(chess_move);
+        self.redo_stack.clear();
+
+        Ok(())
+    }
+
+    pub fn undo_move(&mut self) -> Result<(), &'static str> {
+        let chess_move = self.unmake_move()?;
+        self.redo_stack.push(chess_move);
+
+        Ok(())
+    }
+
+    pub fn redo_move(&mut self) -> Result<(), &'static str> {
+        let chess_move = self.redo_stack.pop().ok_or("No moves to redo!")?;
+        self.apply_move(&chess_move);
+
+        Ok(())
+    }
+
+    fn apply_move(&mut self, chess_move: &Move) {
+        self.make_move(chess_move);
+        self.check_game_ended();
+    }
+
+    // Reverts the last made move in place, restoring the state exactly
+    pub fn unmake_move(&mut self) -> Result<Move, &'static str> {
+        let entry = self.history.pop().ok_or("No moves to undo!")?;
+        let chess_move = entry.chess_move;
+        let (from, to) = (chess_move.from, chess_move.to);
+
+        // Rook is lifted first since in Chess960 the king may stand on its starting square
+        let castle_rook = chess_move
+            .castle_rook_positions()
+            .and_then(|(rook_from, rook_to)| self.board.remove(&rook_to).map(|rook| (rook_from, rook)));
+
+        self.board.remove(&to);
+        if !chess_move.is_drop() {
+            self.board.insert(from, chess_move.piece);
         }
 
+        if let (Some(captured), Some(position)) = (chess_move.captured, chess_move.capture_position()) {
+            self.board.insert(position, captured);
+        }
+
+        if let Some((rook_from, rook)) = castle_rook {
+            self.board.insert(rook_from, rook);
+        }
+
+        for (position, piece) in entry.exploded {
+            self.board.insert(position, piece);
+        }
+
+        self.castles = entry.castles;
+        self.en_pasant_position = entry.en_pasant_position;
+        self.half_moves = entry.half_moves;
+        self.full_moves = entry.full_moves;
+        self.result = entry.result;
+        self.pockets = entry.pockets;
+        self.promoted = entry.promoted;
+        self.checks = entry.checks;
+        self.whose_move = chess_move.piece.color;
+        self.position_history.pop();
+
+        Ok(chess_move)
+    }
+
+    // Plays the move in place without checking its legality or whether the game has ended.
+    // Can be reverted with unmake_move.
+    pub fn make_move(&mut self, chess_move: &Move) {
+        let (from, to) = (chess_move.from, chess_move.to);
+        let color = chess_move.piece.color;
+
+        self.position_history.push(self.repetition_key());
+        self.history.push(HistoryEntry {
+            chess_move: chess_move.clone(),
+            castles: self.castles.clone(),
+            en_pasant_position: self.en_pasant_position,
+            half_moves: self.half_moves,
+            full_moves: self.full_moves,
+            result: self.result.clone(),
+            pockets: self.pockets,
+            promoted: self.promoted,
+            exploded: Vec::new(),
+            checks: self.checks,
+        });
+
         // Executing move
-        let old_piece = self.board.remove(&from);
-        let piece = prom_piece.or(old_piece).ok_or("Piece disappeared somehow!")?;
-        let to_piece = self.board.remove(&to);
+        if let (Some(captured), Some(position)) = (chess_move.captured, chess_move.capture_position()) {
+            self.board.remove(&position);
 
+            // Captured pieces go to the capturer's pocket, promoted ones as pawns
+            if self.variant == Variant::Crazyhouse {
+                let piece_type = if self.promoted & bit(position) != 0 { PieceType::Pawn } else { captured.piece_type };
+                self.pockets[color as usize].add(piece_type);
+            }
+            self.promoted &= !bit(position);
+        }
+
+        if chess_move.is_drop() {
+            // Drop is only made after validation, so the piece is in the pocket
+            let _ = self.pockets[color as usize].remove(chess_move.piece.piece_type);
+        }
+
+        // Castling, lifting the rook before the king possibly lands on its square in Chess960
+        let castle_rook = chess_move
+            .castle_rook_positions()
+            .and_then(|(rook_from, rook_to)| self.board.remove(&rook_from).map(|rook| (rook_to, rook)));
+
+        self.board.remove(&from);
+
+        let piece = chess_move.promotion.map_or(chess_move.piece, |x| Piece::new(x, color));
         self.board.insert(to, piece);
 
+        if let Some((rook_to, rook)) = castle_rook {
+            self.board.insert(rook_to, rook);
+        }
+
+        // Promoted pieces keep their mark wherever they move
+        if self.promoted & bit(from) != 0 || chess_move.is_promotion() {
+            self.promoted = (self.promoted & !bit(from)) | bit(to);
+        }
+
+        // King moving, or rook leaving its square or being captured
+        if chess_move.piece.piece_type == PieceType::King {
+            self.castles.revoke(color);
+        }
+        self.castles.revoke_by_square(from);
+        self.castles.revoke_by_square(to);
+
+        // Atomic capture blows up the capturing piece and every piece but pawns around it
+        if self.variant == Variant::Atomic && chess_move.is_capture() {
+            self.board.remove(&to);
+            self.promoted &= !bit(to);
+
+            let exploded: Vec<(Position, Piece)> = positions(explosion(&self.board, square_of(to)))
+                .filter_map(|position| self.board.remove(&position).map(|piece| (position, piece)))
+                .collect();
+
+            for (position, piece) in &exploded {
+                if piece.piece_type == PieceType::King {
+                    self.castles.revoke(piece.color);
+                }
+                self.castles.revoke_by_square(*position);
+                self.promoted &= !bit(*position);
+            }
+
+            if let Some(entry) = self.history.last_mut() {
+                entry.exploded = exploded;
+            }
+        }
+
         // Increment move counter
-        if clone_piece.color == Color::Black {
+        if color == Color::Black {
             self.full_moves += 1;
         }
 
         self.half_moves += 1;
 
         // If capture or pawn move, reseting 50 move rule
-        if clone_piece.piece_type != PieceType::Pawn || to_piece.is_some() {
+        if chess_move.piece.piece_type == PieceType::Pawn || chess_move.is_capture() {
             self.half_moves = 0;
         }
 
         // Set up en pasant square if pawn moves 2 squares
-        if clone_piece.piece_type == PieceType::Pawn && (to.0 - from.0).abs() == 2 {
+        // Horde pawns pushed from the first rank can't be taken en pasant
+        if chess_move.kind == MoveKind::DoublePush && (from.0 == 1 || from.0 == 6) {
             self.en_pasant_position = Some(((to.0 + from.0) / 2, to.1) as Position);
         } else {
             self.en_pasant_position = None;
         }
 
         // Update turn
-        self.whose_move = if clone_piece.color == Color::White { Color::Black } else { Color::White };
+        self.whose_move = color.opposite();
 
-        self.check_game_ended();
+        if self.variant == Variant::ThreeCheck && self.is_in_check(self.whose_move) {
+            self.checks[color as usize] += 1;
+        }
+    }
 
-        Ok(())
+    // Number of leaf nodes of the legal move tree at the given depth
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut state = self.clone();
+        moves.iter().map(|chess_move| state.perft_child(chess_move, depth - 1)).sum()
+    }
+
+    // Perft split by root moves. At depth 0 the root itself is the only node,
+    // so there are no moves to split it by.
+    pub fn divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        let mut state = self.clone();
+        self.legal_moves()
+            .into_iter()
+            .map(|chess_move| {
+                let nodes = state.perft_child(&chess_move, depth - 1);
+                (chess_move, nodes)
+            })
+            .collect()
+    }
+
+    fn perft_child(&mut self, chess_move: &Move, depth: u32) -> u64 {
+        self.make_move(chess_move);
+
+        let nodes = match depth {
+            0 => 1,
+            1 => self.legal_moves().len() as u64,
+            _ => self.legal_moves().iter().map(|next_move| self.perft_child(next_move, depth - 1)).sum(),
+        };
+
+        self.unmake_move().expect("Move was just made");
+
+        nodes
+    }
+
+    // Win of the side that has just moved by a rule of the variant rather than checkmate
+    fn variant_win(&self) -> Option<WinReason> {
+        let color = self.whose_move.opposite();
+
+        match self.variant {
+            Variant::Atomic if self.find_king(self.whose_move).is_none() => Some(WinReason::KingExploded),
+            Variant::ThreeCheck if self.checks[color as usize] >= 3 => Some(WinReason::ThirdCheck),
+            Variant::KingOfTheHill if self.board.pieces(PieceType::King, color) & HILL != 0 => Some(WinReason::KingOfTheHill),
+            Variant::Horde if self.board.by_color(Color::White) == 0 => Some(WinReason::AllPiecesCaptured),
+            _ => None,
+        }
+    }
+
+    // Racing Kings ends once a king reaches the eighth rank, though after White
+    // does Black gets one more move to equalise for a draw
+    fn race_result(&self) -> Option<GameResult> {
+        let on_goal = |color| self.board.pieces(PieceType::King, color) & GOAL != 0;
+
+        match (on_goal(Color::White), on_goal(Color::Black)) {
+            (true, true) => Some(GameResult::Draw(DrawReason::BothKingsReachedGoal)),
+            (false, true) => Some(GameResult::win(Color::Black, WinReason::KingReachedGoal)),
+            (true, false) => {
+                let can_equalise = self.whose_move == Color::Black
+                    && self.legal_moves().iter().any(|x| x.piece.piece_type == PieceType::King && x.to.0 == 0);
+                if can_equalise { None } else { Some(GameResult::win(Color::White, WinReason::KingReachedGoal)) }
+            },
+            (false, false) => None,
+        }
     }
 
     pub fn check_game_ended(&mut self) {
-        let mut valid_moves: HashSet<Position> = HashSet::new();
         let current_color = self.whose_move;
-        self.board
-            .iter()
-            .filter(|(_, piece)| piece.color == current_color)
-            .for_each(|(pos, piece)| {
-                valid_moves.extend(generate_valid_destinations(self, piece.clone(), *pos));
-            });
-
-        if valid_moves.len() == 0 {
-            // TODO: Move to separate method is_under_check
-            let attacked_fields = generate_attacked_fields(self, current_color);
-            if let Some(king_pos) = self.find_king(current_color) {
-                if attacked_fields.contains(&king_pos) {
-                    self.result = if current_color == Color::White { Some(GameResult::BlackWin) } else { Some(GameResult::WhiteWin) };
+
+        if let Some(reason) = self.variant_win() {
+            self.result = Some(GameResult::win(current_color.opposite(), reason));
+            return;
+        }
+
+        if self.variant == Variant::RacingKings {
+            if let Some(result) = self.race_result() {
+                self.result = Some(result);
+                return;
+            }
+        }
+
+        // Losing every piece or being stalemated wins in Antichess
+        if self.variant == Variant::Antichess && self.legal_moves().is_empty() {
+            let reason = if self.board.by_color(current_color) == 0 { WinReason::AllPiecesLost } else { WinReason::Stalemated };
+            self.result = Some(GameResult::win(current_color, reason));
+            return;
+        }
+
+        if self.legal_moves().is_empty() {
+            // Horde pieces have no king, so they can only be stalemated
+            if self.find_king(current_color).is_some() || self.variant == Variant::Horde {
+                if self.is_in_check(current_color) {
+                    self.result = Some(GameResult::win(current_color.opposite(), WinReason::Checkmate));
                 } else {
                     self.result = Some(GameResult::Stalement);
                 }
             }
+            return;
         }
+
+        if self.half_moves >= 150 {
+            self.result = Some(GameResult::Draw(DrawReason::SeventyFiveMoveRule));
+        } else if self.repetition_count() >= 5 {
+            self.result = Some(GameResult::Draw(DrawReason::FivefoldRepetition));
+        } else if self.is_insufficient_material() {
+            self.result = Some(GameResult::Draw(DrawReason::InsufficientMaterial));
+        }
+    }
+
+    // Draws which are not applied automatically, but one of the players may claim
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.repetition_count() >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if self.half_moves >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    pub fn claim_draw(&mut self) -> Result<(), &'static str> {
+        if self.result.is_some() {
+            return Err("Game is ended!");
+        }
+
+        let reason = self.claimable_draw().ok_or("No draw can be claimed!")?;
+        self.result = Some(GameResult::Draw(reason));
+
+        Ok(())
+    }
+
+    // Identifies the position for repetition purposes: placement, turn, castling and en pasant rights
+    pub fn repetition_key(&self) -> u64 {
+        let mut key = board_key(&self.board) ^ side_key(self.whose_move) ^ castles_key(&self.castles) ^ pockets_key(&self.pockets)
+            ^ checks_key(&self.checks);
+
+        // En pasant square only matters when the capture is actually possible
+        if let Some(en_pasant) = self.en_pasant_position {
+            let can_capture = self.board
+                .iter()
+                .filter(|(pos, piece)| piece.color == self.whose_move && piece.piece_type == PieceType::Pawn && (pos.1 - en_pasant.1).abs() == 1)
+                .any(|(pos, piece)| generate_valid_destinations_bitboard(self, piece, pos) & bit(en_pasant) != 0);
+            if can_capture {
+                key ^= en_pasant_key(en_pasant.1);
+            }
+        }
+
+        key
+    }
+
+    // Number of times the current position has occurred, including the current occurrence
+    pub fn repetition_count(&self) -> usize {
+        let key = self.repetition_key();
+        self.position_history.iter().filter(|x| **x == key).count() + 1
+    }
+
+    // Dead positions: K vs K, K+minor vs K, and kings with bishops all on the same square color
+    pub fn is_insufficient_material(&self) -> bool {
+        // Captured material returns to the board in Crazyhouse, and the remaining
+        // variants aren't won by mating alone, a bare king can still reach the hill
+        if matches!(self.variant, Variant::Crazyhouse | Variant::Antichess | Variant::Horde | Variant::RacingKings | Variant::KingOfTheHill) {
+            return false;
+        }
+
+        // Any piece left can still blow up the enemy king in Atomic or give check in Three-check
+        if matches!(self.variant, Variant::Atomic | Variant::ThreeCheck) {
+            return self.board.iter().all(|(_, piece)| piece.piece_type == PieceType::King);
+        }
+
+        let pieces: Vec<(Position, Piece)> = self.board
+            .iter()
+            .filter(|(_, piece)| piece.piece_type != PieceType::King)
+            .collect();
+
+        if pieces.len() <= 1 {
+            return pieces.iter().all(|(_, piece)| piece.piece_type == PieceType::Bishop || piece.piece_type == PieceType::Knight);
+        }
+
+        let all_bishops = pieces.iter().all(|(_, piece)| piece.piece_type == PieceType::Bishop);
+        let square_colors: HashSet<i8> = pieces.iter().map(|(pos, _)| (pos.0 + pos.1) % 2).collect();
+
+        all_bishops && square_colors.len() == 1
+    }
+}
+
+impl Default for GameState {
+    fn default() -> GameState {
+        GameState::new()
     }
 }
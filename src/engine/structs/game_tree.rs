@@ -0,0 +1,204 @@
+use super::chess_move::Move;
+use super::game_state::GameState;
+use super::pgn_game::{PgnGame, PgnMove};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameNode {
+    // Move leading to this position, None for the root
+    pub chess_move: Option<Move>,
+    pub nags: Vec<u8>,
+    pub comments_before: Vec<String>,
+    pub comments: Vec<String>,
+    // First child continues the mainline, the others are variations
+    pub children: Vec<GameNode>,
+}
+
+impl GameNode {
+    fn new(chess_move: Option<Move>) -> GameNode {
+        GameNode { chess_move, nags: Vec::new(), comments_before: Vec::new(), comments: Vec::new(), children: Vec::new() }
+    }
+
+    // Nodes for the first move of the line followed by its alternatives
+    fn from_pgn_line(line: &[PgnMove]) -> Vec<GameNode> {
+        let (first, rest) = match line.split_first() {
+            Some(split) => split,
+            None => return Vec::new(),
+        };
+
+        let mut node = GameNode::new(Some(first.chess_move.clone()));
+        node.nags = first.nags.clone();
+        node.comments_before = first.comments_before.clone();
+        node.comments = first.comments.clone();
+        node.children = GameNode::from_pgn_line(rest);
+
+        let mut nodes = vec![node];
+        for variation in &first.variations {
+            nodes.extend(GameNode::from_pgn_line(variation));
+        }
+        nodes
+    }
+}
+
+// Moves of a game with all analysed alternatives, together with a cursor
+// pointing at one of its positions
+#[derive(Debug, Clone)]
+pub struct GameTree {
+    root: GameNode,
+    initial_state: GameState,
+    // Position at the current node
+    game_state: GameState,
+    // Child indices leading from the root to the current node
+    path: Vec<usize>,
+}
+
+impl GameTree {
+    pub fn new(initial_state: GameState) -> GameTree {
+        GameTree { root: GameNode::new(None), game_state: initial_state.clone(), initial_state, path: Vec::new() }
+    }
+
+    pub fn from_pgn(game: &PgnGame) -> GameTree {
+        let mut tree = GameTree::new(game.initial_state.clone());
+        tree.root.children = GameNode::from_pgn_line(&game.moves);
+        tree
+    }
+
+    pub fn root(&self) -> &GameNode {
+        &self.root
+    }
+
+    pub fn initial_state(&self) -> &GameState {
+        &self.initial_state
+    }
+
+    pub fn game_state(&self) -> &GameState {
+        &self.game_state
+    }
+
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    pub fn is_at_start(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    // Node reached after the first `depth` steps of the current path
+    fn node_at(&self, depth: usize) -> &GameNode {
+        self.path[..depth].iter().fold(&self.root, |node, index| &node.children[*index])
+    }
+
+    fn node_at_mut(&mut self, depth: usize) -> &mut GameNode {
+        let mut node = &mut self.root;
+        for index in &self.path[..depth] {
+            node = &mut node.children[*index];
+        }
+        node
+    }
+
+    pub fn current(&self) -> &GameNode {
+        self.node_at(self.path.len())
+    }
+
+    pub fn current_mut(&mut self) -> &mut GameNode {
+        self.node_at_mut(self.path.len())
+    }
+
+    pub fn mainline(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            moves.extend(child.chess_move.clone());
+            node = child;
+        }
+        moves
+    }
+
+    // Plays the move from the current position, following the existing node
+    // for it or adding a new one, which becomes a variation if the position
+    // already has a continuation
+    pub fn play_move(&mut self, chess_move: &Move) -> Result<(), &'static str> {
+        self.game_state.play_move(chess_move)?;
+
+        let node = self.current_mut();
+        let index = match node.children.iter().position(|child| child.chess_move.as_ref() == Some(chess_move)) {
+            Some(index) => index,
+            None => {
+                node.children.push(GameNode::new(Some(chess_move.clone())));
+                node.children.len() - 1
+            },
+        };
+        self.path.push(index);
+
+        Ok(())
+    }
+
+    pub fn enter_variation(&mut self, index: usize) -> Result<(), &'static str> {
+        let chess_move = self.current().children.get(index).ok_or("No such variation!")?.chess_move.clone();
+        self.game_state.play_move(&chess_move.ok_or("Node without a move!")?)?;
+        self.path.push(index);
+
+        Ok(())
+    }
+
+    pub fn forward(&mut self) -> Result<(), &'static str> {
+        if self.current().children.is_empty() {
+            return Err("No moves to go forward!");
+        }
+        self.enter_variation(0)
+    }
+
+    pub fn back(&mut self) -> Result<(), &'static str> {
+        if self.path.is_empty() {
+            return Err("Already at the start!");
+        }
+
+        self.game_state.unmake_move()?;
+        self.path.pop();
+
+        Ok(())
+    }
+
+    pub fn to_start(&mut self) {
+        self.game_state = self.initial_state.clone();
+        self.path.clear();
+    }
+
+    // Follows the current line to its last move
+    pub fn to_end(&mut self) {
+        while self.forward().is_ok() {}
+    }
+
+    // Moves the variation containing the current node one place towards the mainline
+    pub fn promote_variation(&mut self) -> Result<(), &'static str> {
+        let depth = self.path.iter().rposition(|index| *index != 0).ok_or("Already on the mainline!")?;
+        let index = self.path[depth];
+
+        self.node_at_mut(depth).children.swap(index, index - 1);
+        self.path[depth] = index - 1;
+
+        Ok(())
+    }
+
+    // Moves the line containing the current node one place away from the mainline
+    pub fn demote_variation(&mut self) -> Result<(), &'static str> {
+        let depth = (0..self.path.len())
+            .rev()
+            .find(|depth| self.path[*depth] + 1 < self.node_at(*depth).children.len())
+            .ok_or("Already the last variation!")?;
+        let index = self.path[depth];
+
+        self.node_at_mut(depth).children.swap(index, index + 1);
+        self.path[depth] = index + 1;
+
+        Ok(())
+    }
+
+    // Removes the current move with everything after it and goes back to the previous position
+    pub fn delete_variation(&mut self) -> Result<(), &'static str> {
+        let index = *self.path.last().ok_or("Nothing to delete at the start!")?;
+        self.back()?;
+        self.current_mut().children.remove(index);
+
+        Ok(())
+    }
+}
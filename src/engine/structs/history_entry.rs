@@ -0,0 +1,22 @@
+use super::castles_state::CastlesState;
+use super::chess_move::Move;
+use super::enums::{Position, GameResult};
+use super::piece::Piece;
+use super::pocket::Pocket;
+use crate::engine::bitboard::Bitboard;
+
+// Played move together with the irreversible state it overwrote
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub chess_move: Move,
+    pub castles: CastlesState,
+    pub en_pasant_position: Option<Position>,
+    pub half_moves: u16,
+    pub full_moves: u16,
+    pub result: Option<GameResult>,
+    pub pockets: [Pocket; 2],
+    pub promoted: Bitboard,
+    // Pieces around the destination blown up by an Atomic capture
+    pub exploded: Vec<(Position, Piece)>,
+    pub checks: [u8; 2],
+}
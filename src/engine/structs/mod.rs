@@ -1,5 +1,18 @@
 pub mod castles_state;
+pub mod chess_move;
 pub mod enums;
+pub mod epd_error;
+pub mod epd_record;
+pub mod epd_report;
+pub mod fen_error;
 pub mod board_map;
 pub mod game_state;
+pub mod game_tree;
+pub mod history_entry;
+pub mod pgn_error;
+pub mod pgn_game;
+pub mod pgn_metadata;
 pub mod piece;
+pub mod pin;
+pub mod pocket;
+pub mod san_error;
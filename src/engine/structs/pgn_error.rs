@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl PgnError {
+    pub fn new(line: usize, column: usize, message: String) -> PgnError {
+        PgnError { line, column, message }
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
@@ -0,0 +1,33 @@
+use super::chess_move::Move;
+use super::game_state::GameState;
+
+#[derive(Debug, Clone)]
+pub struct PgnMove {
+    pub san: String,
+    pub chess_move: Move,
+    // Numeric annotation glyphs, suffixes like !? are stored as their NAG
+    pub nags: Vec<u8>,
+    // Comments preceding the move, only present at the start of a game or variation
+    pub comments_before: Vec<String>,
+    pub comments: Vec<String>,
+    // Alternatives to this move, each starting from the position before it
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PgnGame {
+    // Tags in the order they appear, including the seven tag roster
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<PgnMove>,
+    pub result: Option<String>,
+    // Position the movetext starts from, given by the FEN tag or the standard one
+    pub initial_state: GameState,
+    // Position after the mainline
+    pub game_state: GameState,
+}
+
+impl PgnGame {
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+}
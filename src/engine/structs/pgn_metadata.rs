@@ -0,0 +1,27 @@
+// Game information written to the tag section of exported PGN
+#[derive(Debug, Clone)]
+pub struct PgnMetadata {
+    pub event: String,
+    pub site: String,
+    pub date: String,
+    pub round: String,
+    pub white: String,
+    pub black: String,
+    // Value of the Termination tag, derived from the result when missing
+    pub termination: Option<String>,
+}
+
+impl Default for PgnMetadata {
+    // Unknown values as the PGN standard writes them
+    fn default() -> PgnMetadata {
+        PgnMetadata {
+            event: "?".to_string(),
+            site: "?".to_string(),
+            date: "????.??.??".to_string(),
+            round: "?".to_string(),
+            white: "?".to_string(),
+            black: "?".to_string(),
+            termination: None,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt;
 // use std::char::from_u32;
 use std::clone::Clone;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Piece {
     pub piece_type: PieceType,
     pub color: Color,
@@ -11,10 +11,10 @@ pub struct Piece {
 
 impl Piece {
     pub fn new(piece_type: PieceType, color: Color) -> Piece {
-        Piece { piece_type: piece_type, color: color }
+        Piece { piece_type, color }
     }
 
-    pub fn from_fen_char(fen_char: char) -> Piece {
+    pub fn from_fen_char(fen_char: char) -> Option<Piece> {
         let color = if fen_char.is_lowercase() { Color::Black } else { Color::White };
         let letter = fen_char.to_ascii_uppercase();
         let piece_type = match letter {
@@ -24,10 +24,10 @@ impl Piece {
             'B' => PieceType::Bishop,
             'N' => PieceType::Knight,
             'P' => PieceType::Pawn,
-            _ => PieceType::Pawn,
+            _ => return None,
         };
 
-        Piece::new(piece_type, color)
+        Some(Piece::new(piece_type, color))
     }
 
     pub fn to_fen_char(&self) -> char {
@@ -40,9 +40,7 @@ impl Piece {
             PieceType::Pawn => { 'P' },
         };
 
-        let letter = if self.color == Color::Black { letter.to_ascii_lowercase() } else { letter };
-
-        letter
+        if self.color == Color::Black { letter.to_ascii_lowercase() } else { letter }
     }
 }
 
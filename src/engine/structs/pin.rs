@@ -0,0 +1,9 @@
+use super::enums::Position;
+
+// Piece pinned to its king, the ray runs from the king (exclusive) up to the pinner (inclusive)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pin {
+    pub pinned: Position,
+    pub pinner: Position,
+    pub ray: Vec<Position>,
+}
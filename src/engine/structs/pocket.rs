@@ -0,0 +1,55 @@
+use super::enums::{Color, PieceType};
+use super::piece::Piece;
+
+// Most pieces of one type a side can hold, as it never has more than 16 pieces
+pub const MAX_COUNT: u8 = 16;
+
+// Order pieces are listed in FEN and shown by the renderer
+const POCKET_ORDER: [PieceType; 5] = [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight, PieceType::Pawn];
+
+// Captured pieces a side can drop back onto the board in Crazyhouse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pocket {
+    counts: [u8; 6],
+}
+
+impl Pocket {
+    pub fn new() -> Pocket {
+        Pocket { counts: [0; 6] }
+    }
+
+    pub fn count(&self, piece_type: PieceType) -> u8 {
+        self.counts[piece_type as usize]
+    }
+
+    pub fn add(&mut self, piece_type: PieceType) {
+        self.counts[piece_type as usize] += 1;
+    }
+
+    pub fn remove(&mut self, piece_type: PieceType) -> Result<(), &'static str> {
+        let count = &mut self.counts[piece_type as usize];
+        if *count == 0 {
+            return Err("Piece is missing from the pocket!");
+        }
+
+        *count -= 1;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|x| *x == 0)
+    }
+
+    // Types of the pieces available for dropping
+    pub fn piece_types(&self) -> impl Iterator<Item = PieceType> + '_ {
+        POCKET_ORDER.into_iter().filter(|x| self.count(*x) > 0)
+    }
+
+    // Pieces as FEN letters, e.g. QNNP for White or nnp for Black
+    pub fn to_fen_str(&self, color: Color) -> String {
+        POCKET_ORDER
+            .iter()
+            .flat_map(|x| std::iter::repeat_n(Piece::new(*x, color).to_fen_char(), self.count(*x) as usize))
+            .collect()
+    }
+}
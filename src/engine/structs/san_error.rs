@@ -0,0 +1,24 @@
+use super::chess_move::Move;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SanError {
+    Invalid(String),
+    Illegal(String),
+    MissingPromotion(String),
+    Ambiguous { san: String, candidates: Vec<Move> },
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SanError::Invalid(san) => write!(f, "Invalid move syntax '{}'", san),
+            SanError::Illegal(san) => write!(f, "Illegal move '{}'", san),
+            SanError::MissingPromotion(san) => write!(f, "Missing promotion piece in '{}'", san),
+            SanError::Ambiguous { san, candidates } => {
+                let candidates: Vec<String> = candidates.iter().map(|x| x.to_string()).collect();
+                write!(f, "Ambiguous move '{}', candidates: {}", san, candidates.join(", "))
+            },
+        }
+    }
+}
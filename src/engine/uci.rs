@@ -0,0 +1,53 @@
+use super::structs::chess_move::Move;
+use super::structs::enums::{Color, Position};
+use super::san::parse_san;
+use super::structs::game_state::GameState;
+use super::structs::piece::Piece;
+use super::utils::{couple_to_std_pos, std_pos_to_couple};
+
+// Reads UCI long algebraic notation, e.g. e2e4 or e7e8q, or e7e8k in Antichess. Castling is written as the king's move, e.g. e1g1,
+// or in Chess960 as the king moving onto its rook, e.g. b1a1.
+pub fn parse_uci(input: &str) -> Result<(Position, Position, Option<Piece>), &'static str> {
+    let input = input.trim();
+    if !input.is_ascii() || (input.len() != 4 && input.len() != 5) {
+        return Err("Invalid UCI move!");
+    }
+
+    let from = std_pos_to_couple(input[0..2].to_string())?;
+    let to = std_pos_to_couple(input[2..4].to_string())?;
+    let promotion_piece = match input.chars().nth(4) {
+        Some(letter @ ('q' | 'r' | 'b' | 'n' | 'k')) => Piece::from_fen_char(letter),
+        Some(_) => return Err("Invalid promotion piece!"),
+        None => None,
+    };
+
+    Ok((from, to, promotion_piece))
+}
+
+// Resolves a UCI move against the legal moves of the position. Crazyhouse drops
+// are written the same as in SAN, e.g. N@f3.
+pub fn parse_uci_move(game_state: &GameState, input: &str) -> Result<Move, &'static str> {
+    if input.contains('@') {
+        return parse_san(game_state, input).map_err(|_| "Invalid drop!");
+    }
+
+    let (from, to, promotion_piece) = parse_uci(input)?;
+    game_state.find_move(from, to, promotion_piece)
+}
+
+pub fn to_uci(chess_move: &Move) -> String {
+    if chess_move.is_drop() {
+        let letter = Piece::new(chess_move.piece.piece_type, Color::White).to_fen_char();
+        return format!("{}@{}", letter, couple_to_std_pos(chess_move.to));
+    }
+
+    let to = match chess_move.castle_rook {
+        Some(rook) if !chess_move.is_standard_castle() => rook,
+        _ => chess_move.to,
+    };
+    let mut uci = format!("{}{}", couple_to_std_pos(chess_move.from), couple_to_std_pos(to));
+    if let Some(promotion) = chess_move.promotion {
+        uci.push(Piece::new(promotion, Color::Black).to_fen_char());
+    }
+    uci
+}
@@ -1,21 +1,45 @@
 use super::structs::enums::Position;
 use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn std_pos_to_couple (std_pos: String) -> Result<Position, &'static str> {
   let bytes = std_pos.as_bytes();
+  if bytes.len() != 2 {
+    return Err("Invalid Position!");
+  }
   let row = bytes[0] as i8;
   let col = bytes[1] as i8;
-  if row < 97 || row > 105 || col > 56 || col < 48 {
+  if !(97..=104).contains(&row) || !(49..=56).contains(&col) {
     return Err("Invalid Position!");
   }
   Ok((56 - bytes[1] as i8 , (bytes[0] as i8 - 97)))
 }
 
+pub fn couple_to_std_pos (position: Position) -> String {
+  format!("{}{}", (b'a' + position.1 as u8) as char, 8 - position.0)
+}
+
 pub fn is_in_bounds (position: Position) -> bool { position.0 >= 0 && position.0 < 8 && position.1 >= 0 && position.1 < 8 }
 
 pub fn read_ln () -> String {
   let mut tmp = String::new();
   io::stdin().read_line(&mut tmp).expect("Did not enter a correct string");
 
-  return tmp;
+  tmp
+}
+
+// Today's UTC date as YYYY.MM.DD, converted from days since the epoch
+pub fn current_date () -> String {
+  let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |x| x.as_secs());
+  let days = (seconds / 86400) as i64 + 719468;
+  let era = days.div_euclid(146097);
+  let day_of_era = days.rem_euclid(146097);
+  let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let month_index = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+  let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+  let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+  format!("{:04}.{:02}.{:02}", year, month, day)
 }
@@ -0,0 +1,71 @@
+use super::bitboard::square_of;
+use super::structs::board_map::BoardMap;
+use super::structs::castles_state::CastlesState;
+use super::structs::enums::{Color, Position, PieceType};
+use super::structs::piece::Piece;
+use super::structs::pocket::Pocket;
+
+const PIECE_KEYS_COUNT: usize = 2 * 6 * 64;
+const KEYS_COUNT: usize = PIECE_KEYS_COUNT + 1 + 4 + 8 + 2 * 6 + 2;
+
+const fn generate_keys() -> [u64; KEYS_COUNT] {
+    let mut keys = [0; KEYS_COUNT];
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut i = 0;
+    while i < KEYS_COUNT {
+        // splitmix64
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        keys[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    keys
+}
+
+static KEYS: [u64; KEYS_COUNT] = generate_keys();
+const SIDE_KEY: usize = PIECE_KEYS_COUNT;
+const CASTLES_KEY: usize = SIDE_KEY + 1;
+const EN_PASANT_KEY: usize = CASTLES_KEY + 4;
+const POCKET_KEY: usize = EN_PASANT_KEY + 8;
+const CHECKS_KEY: usize = POCKET_KEY + 2 * 6;
+
+pub fn piece_key(piece: Piece, position: Position) -> u64 {
+    KEYS[(piece.color as usize * 6 + piece.piece_type as usize) * 64 + square_of(position)]
+}
+
+pub fn board_key(board: &BoardMap) -> u64 {
+    board.iter().fold(0, |key, (position, piece)| key ^ piece_key(piece, position))
+}
+
+pub fn side_key(color: Color) -> u64 {
+    if color == Color::Black { KEYS[SIDE_KEY] } else { 0 }
+}
+
+pub fn castles_key(castles: &CastlesState) -> u64 {
+    [castles.white_king, castles.white_queen, castles.black_king, castles.black_queen]
+        .iter()
+        .enumerate()
+        .filter(|(_, right)| right.is_some())
+        .fold(0, |key, (i, _)| key ^ KEYS[CASTLES_KEY + i])
+}
+
+pub fn en_pasant_key(column: i8) -> u64 {
+    KEYS[EN_PASANT_KEY + column as usize]
+}
+
+// Crazyhouse pockets, each piece count scales the key of its type
+pub fn pockets_key(pockets: &[Pocket; 2]) -> u64 {
+    let piece_types = [PieceType::Pawn, PieceType::King, PieceType::Queen, PieceType::Knight, PieceType::Rook, PieceType::Bishop];
+    pockets.iter().enumerate().fold(0, |key, (color, pocket)| {
+        piece_types.iter().fold(key, |key, piece_type| {
+            key ^ KEYS[POCKET_KEY + color * 6 + *piece_type as usize].wrapping_mul(pocket.count(*piece_type) as u64)
+        })
+    })
+}
+
+// Three-check counters, scaling the key of each side by its checks given
+pub fn checks_key(checks: &[u8; 2]) -> u64 {
+    checks.iter().enumerate().fold(0, |key, (color, count)| key ^ KEYS[CHECKS_KEY + color].wrapping_mul(*count as u64))
+}
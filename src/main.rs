@@ -1,46 +1,324 @@
-mod engine;
-
-use engine::structs::game_state::GameState;
-use engine::parser::{parse, parse_move};
-use engine::utils::read_ln;
-use engine::renderer::render_board;
+use rust_sfml_chess::clear_view;
+use rust_sfml_chess::engine::structs::game_state::GameState;
+use rust_sfml_chess::engine::chess960::{random_start_position, start_position};
+use rust_sfml_chess::engine::epd::run_epd_suite;
+use rust_sfml_chess::engine::parser::{parse, validate_position, parse_move, INITIAL_FEN};
+use rust_sfml_chess::engine::structs::enums::Variant;
+use rust_sfml_chess::engine::pgn_reader::PgnReader;
+use rust_sfml_chess::engine::pgn_writer::write_pgn;
+use rust_sfml_chess::engine::structs::pgn_metadata::PgnMetadata;
+use rust_sfml_chess::engine::utils::{current_date, read_ln};
+use rust_sfml_chess::engine::renderer::render_board;
+use rust_sfml_chess::engine::san::parse_san;
+use rust_sfml_chess::engine::uci::{parse_uci, parse_uci_move};
+use std::env;
 use std::fs;
+use std::io::BufReader;
+
+const CRAZYHOUSE_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1";
+const ANTICHESS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1";
+const HORDE_FEN: &str = "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+const RACING_KINGS_FEN: &str = "8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1";
 
 fn main() {
-    let contents = &mut fs::read_to_string("./src/static/initial.fen")
-        .expect("Something went wrong reading the file");
+    let args: Vec<String> = env::args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("perft") => run_perft(&args[2..], false),
+        Some("divide") => run_perft(&args[2..], true),
+        Some("pgn") => run_pgn(&args[2..]),
+        Some("epd") => run_epd(&args[2..]),
+        Some("chess960") => play_chess960(&args[2..]),
+        Some("crazyhouse") => play(CRAZYHOUSE_FEN, Variant::Crazyhouse),
+        Some("atomic") => play(INITIAL_FEN, Variant::Atomic),
+        Some("three-check") => play(INITIAL_FEN, Variant::ThreeCheck),
+        Some("king-of-the-hill") => play(INITIAL_FEN, Variant::KingOfTheHill),
+        Some("antichess") => play(ANTICHESS_FEN, Variant::Antichess),
+        Some("horde") => play(HORDE_FEN, Variant::Horde),
+        Some("racing-kings") => play(RACING_KINGS_FEN, Variant::RacingKings),
+        _ => {
+            let contents = fs::read_to_string("./src/static/initial.fen")
+                .expect("Something went wrong reading the file");
+            play(contents.trim(), Variant::Standard);
+        },
+    }
+}
+
+// Usage: chess960 [index], a random start position is picked without an index
+fn play_chess960(args: &[String]) {
+    let fen = match args.first().map(|x| x.parse::<u16>()) {
+        None => random_start_position().1,
+        Some(Ok(index)) => match start_position(index) {
+            Ok(fen) => fen,
+            Err(err) => {
+                println!("{}", err);
+                return;
+            },
+        },
+        Some(Err(_)) => {
+            println!("Usage: chess960 [index]");
+            return;
+        },
+    };
 
-    let contents = &mut contents.trim().to_string();
+    play(&fen, Variant::Standard);
+}
+
+// Usage: perft|divide "<fen>" <depth>
+fn run_perft(args: &[String], divide: bool) {
+    if args.len() != 2 {
+        println!("Usage: perft|divide \"<fen>\" <depth>");
+        return;
+    }
+
+    let depth = match args[1].parse::<u32>() {
+        Ok(depth) => depth,
+        Err(_) => {
+            println!("Invalid depth: {}", args[1]);
+            return;
+        },
+    };
 
     let game_state = &mut GameState::new();
+    if let Err(err) = parse(args[0].trim(), game_state) {
+        println!("Invalid FEN: {}", err);
+        return;
+    }
+
+    if divide && depth > 0 {
+        let mut nodes = 0;
+        for (chess_move, count) in game_state.divide(depth) {
+            println!("{}: {}", chess_move, count);
+            nodes += count;
+        }
+        println!();
+        println!("Nodes searched: {}", nodes);
+    } else {
+        println!("Nodes searched: {}", game_state.perft(depth));
+    }
+}
+
+// Usage: pgn <file>
+fn run_pgn(args: &[String]) {
+    if args.len() != 1 {
+        println!("Usage: pgn <file>");
+        return;
+    }
+
+    let file = match fs::File::open(&args[0]) {
+        Ok(file) => file,
+        Err(err) => {
+            println!("Error while opening {}: {}", args[0], err);
+            return;
+        },
+    };
+
+    for (index, game) in PgnReader::new(BufReader::new(file)).enumerate() {
+        match game {
+            Ok(game) => println!(
+                "Game {}: {} - {}, {}, {} moves, {}",
+                index + 1,
+                game.tag("White").unwrap_or("?"),
+                game.tag("Black").unwrap_or("?"),
+                game.result.as_deref().unwrap_or("*"),
+                game.moves.len(),
+                game.game_state.to_fen(),
+            ),
+            Err(err) => println!("Game {}: {}", index + 1, err),
+        }
+    }
+}
 
-    parse(contents, game_state);
+// Usage: epd <file> [ask|first]
+// Answers are typed in by the user with ask, first picks the first legal move
+fn run_epd(args: &[String]) {
+    if args.is_empty() || args.len() > 2 {
+        println!("Usage: epd <file> [ask|first]");
+        return;
+    }
+
+    let ask = match args.get(1).map(String::as_str) {
+        None | Some("ask") => true,
+        Some("first") => false,
+        _ => {
+            println!("Usage: epd <file> [ask|first]");
+            return;
+        },
+    };
+
+    let file = match fs::File::open(&args[0]) {
+        Ok(file) => file,
+        Err(err) => {
+            println!("Error while opening {}: {}", args[0], err);
+            return;
+        },
+    };
+
+    let report = run_epd_suite(BufReader::new(file), |record| {
+        if !ask {
+            return record.game_state.legal_moves().into_iter().next();
+        }
+
+        let game_state = &mut record.game_state.clone();
+        loop {
+            render_board(game_state);
+            println!("{}: enter your move, empty to give up", record.id.as_deref().unwrap_or("?"));
+            let move_str = read_ln();
+            if move_str.trim().is_empty() {
+                return None;
+            }
+
+            match parse_san(game_state, &move_str).map_err(|err| err.to_string()).or_else(|_| parse_uci_move(game_state, &move_str).map_err(str::to_string)) {
+                Ok(chess_move) => return Some(chess_move),
+                Err(err) => println!("Error while parsing: {}", err),
+            }
+        }
+    });
+
+    let report = match report {
+        Ok(report) => report,
+        Err(err) => {
+            println!("Error while reading {}: {}", args[0], err);
+            return;
+        },
+    };
+
+    for (line, err) in &report.errors {
+        println!("Line {}: {}", line, err);
+    }
+
+    for result in &report.results {
+        let expected = if result.avoid_moves.is_empty() {
+            format!("bm {}", result.best_moves.join(" "))
+        } else if result.best_moves.is_empty() {
+            format!("am {}", result.avoid_moves.join(" "))
+        } else {
+            format!("bm {}, am {}", result.best_moves.join(" "), result.avoid_moves.join(" "))
+        };
+        println!(
+            "{}: {} {} ({})",
+            result.id.as_deref().unwrap_or("?"),
+            if result.passed { "passed" } else { "failed" },
+            result.chosen.as_deref().unwrap_or("-"),
+            expected,
+        );
+    }
+    println!("Score: {}/{}", report.passed(), report.total());
+}
+
+// Asks for a file to save the finished game to, returns the outcome to show
+fn offer_save(game_state: &GameState) -> Option<String> {
+    println!("Save the game as PGN? Enter a file name or leave empty to skip:");
+    let file_name = read_ln();
+    let file_name = file_name.trim();
+    if file_name.is_empty() {
+        return None;
+    }
+
+    let metadata = PgnMetadata {
+        event: "Casual game".to_string(),
+        date: current_date(),
+        white: "White".to_string(),
+        black: "Black".to_string(),
+        ..PgnMetadata::default()
+    };
+
+    match fs::write(file_name, write_pgn(game_state, &metadata)) {
+        Ok(()) => Some(format!("Game saved to {}", file_name)),
+        Err(err) => Some(format!("Error while saving the game: {}", err)),
+    }
+}
+
+fn play(fen: &str, variant: Variant) {
+    let game_state = &mut GameState::new();
+
+    // Variant is set before validation, as it decides which positions are legal.
+    // Standard keeps whatever the FEN implies, e.g. Crazyhouse pockets.
+    let parsed = parse(fen, game_state).and_then(|_| {
+        if variant != Variant::Standard {
+            game_state.variant = variant;
+        }
+        validate_position(game_state)
+    });
+    if let Err(err) = parsed {
+        println!("Invalid FEN: {}", err);
+        return;
+    }
 
-    // FIXME: Implement castlign
-    // TODO: add to fen_string functionality
-    // TODO: add short move syntax (Disambiguate :( )
     // TODO: Basic sfml integration
     // TODO: UI, Click, highlight
     // TODO: Standardized API
     // TODO: Custom evaluation and bot
     // TODO: Talking with stockfish
     // TODO: Menu audio etc.
-    // TODO: Undo move.
+    // Error of the last command, shown below the board since the view is cleared on every turn
+    let mut status: Option<String> = None;
+    // Saving is offered once per ending, again only if the ending is undone
+    let mut save_offered = false;
     loop {
         clear_view!();
         render_board(game_state);
+        if let Some(message) = status.take() {
+            println!("{}", message);
+        }
+
+        if game_state.result.is_none() {
+            save_offered = false;
+        } else if !save_offered {
+            save_offered = true;
+            status = offer_save(game_state);
+            continue;
+        }
 
         let move_str = read_ln();
+        match move_str.trim() {
+            "draw" => {
+                if let Err(err) = game_state.claim_draw() {
+                    status = Some(format!("Error while claiming a draw: {}", err));
+                }
+                continue;
+            },
+            "undo" => {
+                if let Err(err) = game_state.undo_move() {
+                    status = Some(format!("Error while undoing the move: {}", err));
+                }
+                continue;
+            },
+            "redo" => {
+                if let Err(err) = game_state.redo_move() {
+                    status = Some(format!("Error while redoing the move: {}", err));
+                }
+                continue;
+            },
+            _ => {},
+        }
+
+        // Coordinate syntax e2:e4 or e7:e8:q, UCI e2e4 or e7e8q, anything else is read as SAN
+        if !move_str.contains(':') {
+            let parsed = match parse_uci_move(game_state, &move_str) {
+                Ok(chess_move) => Ok(chess_move),
+                Err(err) if parse_uci(&move_str).is_ok() => Err(err.to_string()),
+                Err(_) => parse_san(game_state, &move_str).map_err(|err| err.to_string()),
+            };
+            let result = parsed
+                .map_err(|err| format!("Error while parsing: {}", err))
+                .and_then(|chess_move| {
+                    game_state.play_move(&chess_move).map_err(|err| format!("Error while executing the move: {}", err))
+                });
+            status = result.err();
+            continue;
+        }
+
         let (from, to, prom_piece) = match parse_move(move_str) {
             Ok(t) => t,
             Err(err) => {
-                println!("Error while parsing: {}", err);
+                status = Some(format!("Error while parsing: {}", err));
                 continue;
             },
         };
 
         if let Err(err) = game_state.execute_move(from, to, prom_piece) {
-            println!("Error while executing the move: {}", err);
+            status = Some(format!("Error while executing the move: {}", err));
         }
     }
 }
@@ -0,0 +1,49 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::san::{parse_san, to_san};
+use rust_sfml_chess::engine::structs::enums::{GameResult, PieceType, Variant, WinReason};
+use rust_sfml_chess::engine::uci::parse_uci_move;
+
+#[test]
+fn counts_antichess_positions() {
+    assert_eq!(game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1", Variant::Antichess).perft(4), 153299);
+}
+
+#[test]
+fn captures_are_compulsory() {
+    let game_state = &mut game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1", Variant::Antichess);
+    for san in ["e4", "d5"] {
+        play(game_state, san);
+    }
+
+    let moves = game_state.legal_moves();
+    assert_eq!(moves.len(), 1);
+    assert_eq!(to_san(game_state, &moves[0]), "exd5");
+    assert!(game_state.execute_move((6, 0), (5, 0), None).is_err());
+}
+
+#[test]
+fn king_can_be_captured_and_promoted_to() {
+    // King walks into an attack without any check
+    let game_state = game("8/8/8/8/3k4/8/4P3/4K3 b - - 0 1", Variant::Antichess);
+    assert!(!game_state.is_in_check(game_state.whose_move));
+    assert!(parse_san(&game_state, "Kd3").is_ok());
+
+    let game_state = &mut game("8/P7/8/8/8/8/8/k7 w - - 0 1", Variant::Antichess);
+    let chess_move = parse_uci_move(game_state, "a7a8k").unwrap();
+    assert_eq!(chess_move.promotion, Some(PieceType::King));
+    assert_eq!(to_san(game_state, &chess_move), "a8=K");
+}
+
+#[test]
+fn losing_every_piece_wins() {
+    let game_state = &mut game("8/8/8/8/8/1p6/P7/8 w - - 0 1", Variant::Antichess);
+    play(game_state, "axb3");
+    assert!(matches!(game_state.result, Some(GameResult::BlackWin(WinReason::AllPiecesLost))));
+
+    // Black pawn is blocked, leaving Black stalemated
+    let game_state = &mut game("8/8/8/8/8/p7/P7/8 b - - 0 1", Variant::Antichess);
+    game_state.check_game_ended();
+    assert!(matches!(game_state.result, Some(GameResult::BlackWin(WinReason::Stalemated))));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::san::{parse_san, to_san};
+use rust_sfml_chess::engine::structs::enums::{GameResult, Variant, WinReason};
+
+#[test]
+fn counts_atomic_positions() {
+    assert_eq!(game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Variant::Atomic).perft(4), 197326);
+    assert_eq!(game("rn2kb1r/1pp1p2p/p2q1pp1/3P4/2P3b1/4PN2/PP3PPP/R2QKB1R b KQkq - 0 1", Variant::Atomic).perft(3), 45237);
+    assert_eq!(game("rn1qkb1r/p5pp/2p5/3p4/N3P3/5P2/PPP4P/R1BQK3 w Qkq - 0 1", Variant::Atomic).perft(3), 23353);
+}
+
+#[test]
+fn explodes_pieces_around_capture() {
+    let game_state = &mut game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Variant::Atomic);
+    for san in ["Nf3", "d5", "Ne5", "Nd7", "Nxd7"] {
+        play(game_state, san);
+    }
+
+    // Both knights, the bishop, queen and king around d7 are gone, pawns stay
+    assert_eq!(game_state.to_fen(), "r4bnr/ppp1pppp/8/3p4/8/8/PPPPPPPP/RNBQKB1R b KQ - 0 3");
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::KingExploded))));
+
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.to_fen(), "r1bqkbnr/pppnpppp/8/3pN3/8/8/PPPPPPPP/RNBQKB1R w KQkq - 2 3");
+    assert!(game_state.result.is_none());
+}
+
+#[test]
+fn kings_may_not_capture_but_may_touch() {
+    let game_state = game("8/8/8/3k4/8/3K4/4p3/8 w - - 0 1", Variant::Atomic);
+    assert!(parse_san(&game_state, "Kxe2").is_err());
+
+    // Neighbouring kings can't be in check
+    let game_state = game("8/8/8/8/3kK3/8/8/3r4 w - - 0 1", Variant::Atomic);
+    assert!(!game_state.is_in_check(game_state.whose_move));
+    assert!(parse_san(&game_state, "Kd3").is_ok());
+}
+
+#[test]
+fn writes_king_explosion_as_mate() {
+    let game_state = game("4k3/4p3/8/8/8/8/8/4KQ2 w - - 0 1", Variant::Atomic);
+    let chess_move = parse_san(&game_state, "Qf7").unwrap();
+    assert_eq!(to_san(&game_state, &chess_move), "Qf7+");
+
+    let game_state = &mut game("4k3/4pq2/8/8/8/8/8/4KQ2 w - - 0 1", Variant::Atomic);
+    let chess_move = parse_san(game_state, "Qxf7").unwrap();
+    assert_eq!(to_san(game_state, &chess_move), "Qxf7#");
+    game_state.play_move(&chess_move).unwrap();
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::KingExploded))));
+}
+
+#[test]
+fn castles_next_to_enemy_king() {
+    // Square covered only by the enemy king is safe to land on
+    let game_state = game("8/8/8/8/8/8/6k1/4K2R w K - 0 1", Variant::Atomic);
+    assert!(parse_san(&game_state, "O-O").is_ok());
+
+    // Touching kings mean the rook on a1 gives no check to castle out of
+    let game_state = game("8/8/8/8/8/8/5k2/r3K2R w K - 0 1", Variant::Atomic);
+    assert!(!game_state.is_in_check(game_state.whose_move));
+    assert!(parse_san(&game_state, "O-O").is_ok());
+
+    // Transit square attacked by an ordinary piece still forbids castling
+    let game_state = game("4k3/8/8/8/8/8/8/4K2R w K - 0 1", Variant::Atomic);
+    assert!(parse_san(&game_state, "O-O").is_ok());
+    let game_state = game("4kr2/8/8/8/8/8/8/4K2R w K - 0 1", Variant::Atomic);
+    assert!(parse_san(&game_state, "O-O").is_err());
+
+    // Chess960 castling onto the square next to the enemy king, the rook on b1 shielding the path
+    assert_eq!(game("8/8/8/8/8/8/2k5/rR4KR w KQ - 0 1", Variant::Atomic).perft(3), 4364);
+}
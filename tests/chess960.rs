@@ -0,0 +1,119 @@
+use rust_sfml_chess::engine::chess960::{random_start_position, start_position, POSITION_COUNT};
+use rust_sfml_chess::engine::parser::{parse, INITIAL_FEN};
+use rust_sfml_chess::engine::pgn_reader::PgnReader;
+use rust_sfml_chess::engine::pgn_writer::write_pgn;
+use rust_sfml_chess::engine::san::{parse_san, to_san};
+use rust_sfml_chess::engine::structs::game_state::GameState;
+use rust_sfml_chess::engine::structs::pgn_metadata::PgnMetadata;
+use rust_sfml_chess::engine::uci::parse_uci_move;
+
+fn perft(fen: &str, depth: u32) -> u64 {
+    let game_state = &mut GameState::new();
+    parse(fen, game_state).unwrap();
+    game_state.perft(depth)
+}
+
+#[test]
+fn counts_chess960_positions() {
+    let positions = [
+        ("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", [21, 528, 12189]),
+        ("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9", [21, 807, 18002]),
+        ("b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9", [20, 479, 10471]),
+    ];
+
+    for (fen, counts) in positions {
+        for (depth, count) in counts.iter().enumerate() {
+            assert_eq!(perft(fen, depth as u32 + 1), *count, "{} at depth {}", fen, depth + 1);
+        }
+    }
+}
+
+#[test]
+fn reads_castling_notations() {
+    for fen in ["4k3/8/8/8/8/8/8/1R2K1R1 w GB - 0 1", "4k3/8/8/8/8/8/8/1R2K1R1 w KQ - 0 1"] {
+        let game_state = &mut GameState::new();
+        parse(fen, game_state).unwrap();
+        assert_eq!(game_state.castles.white_king, Some(6));
+        assert_eq!(game_state.castles.white_queen, Some(1));
+        assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/8/1R2K1R1 w GB - 0 1");
+    }
+}
+
+#[test]
+fn castles_onto_standard_squares() {
+    // King on f1 castles onto g1 where its rook stands
+    let game_state = &mut GameState::new();
+    parse("4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1", game_state).unwrap();
+
+    let king_side = parse_uci_move(game_state, "f1g1").unwrap();
+    assert_eq!(to_san(game_state, &king_side), "O-O");
+    game_state.play_move(&king_side).unwrap();
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/8/R4RK1 b - - 1 1");
+    game_state.unmake_move().unwrap();
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1");
+
+    let queen_side = parse_san(game_state, "O-O-O").unwrap();
+    assert_eq!(queen_side.to_string(), "f1a1");
+    game_state.play_move(&queen_side).unwrap();
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/8/2KR2R1 b - - 1 1");
+}
+
+#[test]
+fn checks_transit_squares() {
+    // Rook on e8 attacks e1 which the king passes when castling queen side from g1
+    let game_state = &mut GameState::new();
+    parse("4r1k1/8/8/8/8/8/8/R5KR w HA - 0 1", game_state).unwrap();
+    assert!(parse_san(game_state, "O-O-O").is_err());
+    assert!(parse_san(game_state, "O-O").is_ok());
+}
+
+#[test]
+fn generates_start_positions() {
+    assert_eq!(start_position(518).unwrap(), INITIAL_FEN);
+    assert_eq!(start_position(0).unwrap(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w HFhf - 0 1");
+    assert!(start_position(POSITION_COUNT).is_err());
+
+    let mut back_ranks: Vec<String> = (0..POSITION_COUNT).map(|x| start_position(x).unwrap()[..8].to_string()).collect();
+    back_ranks.sort();
+    back_ranks.dedup();
+    assert_eq!(back_ranks.len(), 960);
+
+    let (index, fen) = random_start_position();
+    assert_eq!(start_position(index).unwrap(), fen);
+    assert!(perft(&fen, 1) > 0);
+}
+
+#[test]
+fn round_trips_start_positions() {
+    for index in 0..POSITION_COUNT {
+        let fen = start_position(index).unwrap();
+        let game_state = &mut GameState::new();
+        parse(&fen, game_state).unwrap();
+        assert_eq!(game_state.to_fen(), fen);
+
+        // Either plain X-FEN or Shredder-FEN files, never a mix of both
+        let castles = fen.split(' ').nth(2).unwrap();
+        assert!(castles == "KQkq" || castles.chars().all(|x| x.is_ascii_alphabetic() && !"KQkq".contains(x)), "{}", castles);
+    }
+}
+
+#[test]
+fn writes_chess960_variant_tag() {
+    let game_state = &mut GameState::new();
+    parse("4k3/8/8/8/8/8/8/R4KR1 w GA - 0 1", game_state).unwrap();
+    game_state.play_move(&parse_san(game_state, "O-O").unwrap()).unwrap();
+
+    let output = write_pgn(game_state, &PgnMetadata::default());
+    assert!(output.contains("[Variant \"Chess960\"]\n"));
+    let written = PgnReader::new(output.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(written.game_state.to_fen(), game_state.to_fen());
+
+    // King off the e file with rooks in the corners
+    let game_state = &mut GameState::new();
+    parse("r2k3r/8/8/8/8/8/8/R2K3R w KQkq - 0 1", game_state).unwrap();
+    assert!(write_pgn(game_state, &PgnMetadata::default()).contains("[Variant \"Chess960\"]"));
+
+    let game_state = &mut GameState::new();
+    parse(INITIAL_FEN, game_state).unwrap();
+    assert!(!write_pgn(game_state, &PgnMetadata::default()).contains("Variant"));
+}
@@ -0,0 +1,21 @@
+// Not every test crate uses every helper
+#![allow(dead_code)]
+
+use rust_sfml_chess::engine::parser::parse;
+use rust_sfml_chess::engine::san::parse_san;
+use rust_sfml_chess::engine::structs::enums::Variant;
+use rust_sfml_chess::engine::structs::game_state::GameState;
+
+// Position of the FEN played under the given variant
+pub fn game(fen: &str, variant: Variant) -> GameState {
+    let mut game_state = GameState::new();
+    parse(fen, &mut game_state).unwrap();
+    game_state.variant = variant;
+    game_state
+}
+
+// Plays a move given in SAN, failing the test if it isn't legal
+pub fn play(game_state: &mut GameState, san: &str) {
+    let chess_move = parse_san(game_state, san).unwrap();
+    game_state.play_move(&chess_move).unwrap();
+}
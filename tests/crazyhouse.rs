@@ -0,0 +1,71 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::parser::parse;
+use rust_sfml_chess::engine::san::to_san;
+use rust_sfml_chess::engine::structs::enums::{Color, PieceType, Variant};
+use rust_sfml_chess::engine::structs::fen_error::{FenError, FenField};
+use rust_sfml_chess::engine::structs::game_state::GameState;
+use rust_sfml_chess::engine::uci::parse_uci_move;
+
+#[test]
+fn counts_crazyhouse_positions() {
+    let game_state = game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1", Variant::Crazyhouse);
+    assert_eq!(game_state.variant, Variant::Crazyhouse);
+    assert_eq!(game_state.perft(4), 197281);
+
+    assert_eq!(game("2k5/8/8/8/8/8/8/4K3[QRBNPqrbnp] w - - 0 1", Variant::Crazyhouse).perft(2), 75353);
+}
+
+#[test]
+fn moves_captures_into_pocket() {
+    let game_state = &mut game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1", Variant::Crazyhouse);
+    for san in ["e4", "d5", "exd5", "Qxd5", "Nc3", "Qa5"] {
+        play(game_state, san);
+    }
+    assert_eq!(game_state.pockets[Color::White as usize].count(PieceType::Pawn), 1);
+    assert_eq!(game_state.to_fen(), "rnb1kbnr/ppp1pppp/8/q7/8/2N5/PPPP1PPP/R1BQKBNR[Pp] w KQkq - 2 4");
+
+    let drop = parse_uci_move(game_state, "P@e4").unwrap();
+    assert_eq!(to_san(game_state, &drop), "P@e4");
+    game_state.play_move(&drop).unwrap();
+    assert_eq!(game_state.to_fen(), "rnb1kbnr/ppp1pppp/8/q7/4P3/2N5/PPPP1PPP/R1BQKBNR[p] b KQkq - 0 4");
+
+    game_state.unmake_move().unwrap();
+    assert_eq!(game_state.to_fen(), "rnb1kbnr/ppp1pppp/8/q7/8/2N5/PPPP1PPP/R1BQKBNR[Pp] w KQkq - 2 4");
+}
+
+#[test]
+fn restricts_drops() {
+    // Drops have to block the check, and pawns can't be dropped on the back ranks
+    let game_state = game("4k3/8/8/8/8/8/8/r3K3[P] w - - 0 1", Variant::Crazyhouse);
+    assert_eq!(game_state.legal_moves().iter().filter(|x| x.is_drop()).count(), 0);
+
+    let game_state = game("4k3/8/8/8/8/8/8/r3K3[N] w - - 0 1", Variant::Crazyhouse);
+    let drops: Vec<String> = game_state.legal_moves().iter().filter(|x| x.is_drop()).map(|x| x.to_string()).collect();
+    assert_eq!(drops, ["N@b1", "N@c1", "N@d1"]);
+
+    let game_state = game("4k3/8/8/8/8/8/8/4K3[P] w - - 0 1", Variant::Crazyhouse);
+    assert_eq!(game_state.legal_moves().iter().filter(|x| x.is_drop()).count(), 48);
+}
+
+#[test]
+fn demotes_captured_promoted_pieces() {
+    let game_state = &mut game("1r2k3/P7/8/8/8/8/8/4K3[] w - - 0 1", Variant::Crazyhouse);
+    play(game_state, "axb8=Q+");
+    assert_eq!(game_state.to_fen(), "1Q~2k3/8/8/8/8/8/8/4K3[R] b - - 0 1");
+
+    let game_state = &mut game("4k3/8/8/8/8/1n6/8/Q~3K3[] b - - 0 1", Variant::Crazyhouse);
+    play(game_state, "Nxa1");
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/8/8/n3K3[p] w - - 0 2");
+}
+
+#[test]
+fn rejects_overfull_pockets() {
+    let fen = format!("4k3/8/8/8/8/8/8/4K3[{}] w - - 0 1", "P".repeat(300));
+    let game_state = &mut GameState::new();
+    assert!(matches!(parse(&fen, game_state), Err(FenError::InvalidValue { field: FenField::Position, .. })));
+
+    let fen = format!("4k3/8/8/8/8/8/8/4K3[{}] w - - 0 1", "P".repeat(16));
+    assert!(parse(&fen, game_state).is_ok());
+}
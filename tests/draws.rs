@@ -0,0 +1,97 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::structs::enums::{DrawReason, GameResult, Variant};
+
+fn is_draw(result: &Option<GameResult>, reason: DrawReason) -> bool {
+    matches!(result, Some(GameResult::Draw(x)) if *x == reason)
+}
+
+#[test]
+fn fifty_moves_can_be_claimed() {
+    let game_state = &mut game("4k3/8/8/8/8/8/8/R3K3 w - - 98 80", Variant::Standard);
+    play(game_state, "Ra2");
+    assert_eq!(game_state.half_moves, 99);
+    assert_eq!(game_state.claimable_draw(), None);
+    assert_eq!(game_state.claim_draw(), Err("No draw can be claimed!"));
+
+    play(game_state, "Kd8");
+    assert_eq!(game_state.claimable_draw(), Some(DrawReason::FiftyMoveRule));
+    assert!(game_state.result.is_none());
+    game_state.claim_draw().unwrap();
+    assert!(is_draw(&game_state.result, DrawReason::FiftyMoveRule));
+}
+
+#[test]
+fn seventy_five_moves_end_the_game() {
+    let game_state = &mut game("4k3/8/8/8/8/8/8/R3K3 w - - 148 80", Variant::Standard);
+    play(game_state, "Ra2");
+    assert!(game_state.result.is_none());
+
+    play(game_state, "Kd8");
+    assert_eq!(game_state.half_moves, 150);
+    assert!(is_draw(&game_state.result, DrawReason::SeventyFiveMoveRule));
+}
+
+#[test]
+fn repetitions_are_claimed_then_automatic() {
+    let game_state = &mut game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Variant::Standard);
+    let shuffle = ["Nf3", "Nf6", "Ng1", "Ng8"];
+
+    for san in shuffle {
+        play(game_state, san);
+    }
+    assert_eq!(game_state.repetition_count(), 2);
+    assert_eq!(game_state.claimable_draw(), None);
+
+    for san in shuffle {
+        play(game_state, san);
+    }
+    assert_eq!(game_state.repetition_count(), 3);
+    assert_eq!(game_state.claimable_draw(), Some(DrawReason::ThreefoldRepetition));
+    assert!(game_state.result.is_none());
+
+    for san in shuffle {
+        play(game_state, san);
+    }
+    assert_eq!(game_state.repetition_count(), 4);
+    assert!(game_state.result.is_none());
+
+    for san in shuffle {
+        play(game_state, san);
+    }
+    assert_eq!(game_state.repetition_count(), 5);
+    assert!(is_draw(&game_state.result, DrawReason::FivefoldRepetition));
+}
+
+#[test]
+fn detects_insufficient_material() {
+    let game_state = &mut game("4k3/8/8/8/8/8/3r4/4K3 w - - 0 1", Variant::Standard);
+    play(game_state, "Kxd2");
+    assert!(is_draw(&game_state.result, DrawReason::InsufficientMaterial));
+
+    let is_insufficient = |fen: &str| game(fen, Variant::Standard).is_insufficient_material();
+    assert!(is_insufficient("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+    assert!(is_insufficient("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"));
+    assert!(is_insufficient("4k3/8/8/8/8/8/8/4KB2 w - - 0 1"));
+    assert!(!is_insufficient("4k3/8/8/8/8/8/8/4KP2 w - - 0 1"));
+    assert!(!is_insufficient("4k3/8/8/8/8/8/8/4KR2 w - - 0 1"));
+    assert!(!is_insufficient("4k3/8/8/8/8/8/8/3NKN2 w - - 0 1"));
+
+    // Bishops on the same square color can't mate, on opposite colors they can
+    assert!(is_insufficient("2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1"));
+    assert!(is_insufficient("2b1k3/8/8/8/8/8/8/3BKB2 w - - 0 1"));
+    assert!(!is_insufficient("3bk3/8/8/8/8/8/8/4KB2 w - - 0 1"));
+}
+
+#[test]
+fn keys_en_pasant_only_if_capturable() {
+    let key = |fen: &str| game(fen, Variant::Standard).repetition_key();
+
+    // No pawn next to the double pushed one
+    assert_eq!(key("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1"), key("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1"));
+    assert_ne!(key("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1"), key("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1"));
+
+    // Capturing would expose the king along the rank
+    assert_eq!(key("8/8/8/8/k2pP2R/8/8/4K3 b - e3 0 1"), key("8/8/8/8/k2pP2R/8/8/4K3 b - - 0 1"));
+}
@@ -0,0 +1,56 @@
+use rust_sfml_chess::engine::epd::{parse_epd, run_epd_suite};
+use rust_sfml_chess::engine::san::parse_san;
+use rust_sfml_chess::engine::structs::epd_error::EpdError;
+use rust_sfml_chess::engine::structs::fen_error::FenError;
+
+const SUITE: &str = "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";
+8/7p/5k2/5p2/p1p2P2/Pr1pPK2/1P1R3P/8 b - - bm Rxb2; id \"WAC.002\";
+r1b1kb1r/3q1ppp/pBp1pn2/8/Np3P2/5B2/PPP3PP/R2QK2R w KQkq - am Qxd7+; id \"avoid\";
+8/8/8/8 w - - bm e4;
+";
+
+#[test]
+fn parses_opcodes() {
+    let record = parse_epd(
+        "r1b1kb1r/3q1ppp/pBp1pn2/8/Np3P2/5B2/PPP3PP/R2QK2R w KQkq - am Qxd7+; pv Qxd7+ Bxd7; acd 12; dm 3; c1 \"a; b\"; hmvc 4; fmvn 20;",
+    )
+    .unwrap();
+
+    assert_eq!(record.game_state.to_fen(), "r1b1kb1r/3q1ppp/pBp1pn2/8/Np3P2/5B2/PPP3PP/R2QK2R w KQkq - 4 20");
+    assert_eq!(record.avoid_moves.len(), 1);
+    assert_eq!(record.predicted_variation.iter().map(|x| x.to_string()).collect::<Vec<String>>(), ["d1d7", "c8d7"]);
+    assert_eq!(record.analysis_depth, Some(12));
+    assert_eq!(record.direct_mate, Some(3));
+    assert_eq!(record.comments[1].as_deref(), Some("a; b"));
+    assert_eq!(record.operations.len(), 7);
+}
+
+#[test]
+fn reports_errors() {
+    assert_eq!(parse_epd("8/8/8/8/8/8/8/8 w -").unwrap_err(), EpdError::Fen(FenError::FieldCount(3)));
+    assert!(matches!(parse_epd("4k3/8/8/8/8/8/8/4K3 w - - bm Ke3;"), Err(EpdError::InvalidOperand { .. })));
+    assert!(matches!(parse_epd("4k3/8/8/8/8/8/8/4K3 w - - id \"open;"), Err(EpdError::InvalidOperation(_))));
+
+    // Move counters have to fit without being truncated
+    assert_eq!(parse_epd("4k3/8/8/8/8/8/8/4K3 w - - fmvn 65535;").unwrap().game_state.full_moves, 65535);
+    assert!(matches!(parse_epd("4k3/8/8/8/8/8/8/4K3 w - - fmvn 70000;"), Err(EpdError::InvalidOperand { .. })));
+    assert!(matches!(parse_epd("4k3/8/8/8/8/8/8/4K3 w - - hmvc 70000;"), Err(EpdError::InvalidOperand { .. })));
+}
+
+#[test]
+fn scores_suite() {
+    let answers = ["Qg6", "Rxb2", "Qxd7+"];
+    let mut index = 0;
+    let report = run_epd_suite(SUITE.as_bytes(), |record| {
+        let chess_move = parse_san(&record.game_state, answers[index]).ok();
+        index += 1;
+        chess_move
+    })
+    .unwrap();
+
+    assert_eq!(report.results.iter().map(|x| x.passed).collect::<Vec<bool>>(), [true, true, false]);
+    assert_eq!(report.results[0].id.as_deref(), Some("WAC.001"));
+    assert_eq!((report.passed(), report.total()), (2, 3));
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].0, 4);
+}
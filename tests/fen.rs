@@ -0,0 +1,73 @@
+use rust_sfml_chess::engine::parser::{parse, parse_validated};
+use rust_sfml_chess::engine::structs::enums::Color;
+use rust_sfml_chess::engine::structs::fen_error::{FenError, FenField};
+use rust_sfml_chess::engine::structs::game_state::GameState;
+
+const POSITIONS: [&str; 12] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "r3k3/8/8/8/8/8/8/4K2R b Kq - 17 42",
+    "8/8/8/8/8/8/8/K6k w - - 99 150",
+    "4k3/8/8/8/8/8/8/4K3 b - - 0 1",
+    "rnbqkb1r/ppp1pppp/5n2/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+];
+
+fn round_trip(fen: &str) -> String {
+    let game_state = &mut GameState::new();
+    parse(fen, game_state).unwrap();
+    game_state.to_fen()
+}
+
+#[test]
+fn round_trips_parsed_positions() {
+    for fen in POSITIONS {
+        assert_eq!(round_trip(fen), fen);
+    }
+}
+
+#[test]
+fn reflects_played_moves() {
+    let game_state = &mut GameState::new();
+    parse(POSITIONS[0], game_state).unwrap();
+    game_state.execute_move((6, 4), (4, 4), None).unwrap();
+    assert_eq!(game_state.to_fen(), POSITIONS[1]);
+
+    game_state.execute_move((1, 2), (3, 2), None).unwrap();
+    assert_eq!(game_state.to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2");
+
+    game_state.execute_move((7, 6), (5, 5), None).unwrap();
+    assert_eq!(game_state.to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+}
+
+#[test]
+fn reports_syntax_errors() {
+    let game_state = &mut GameState::new();
+    assert_eq!(parse("8/8/8/8/8/8/8/8 w - - 0", game_state), Err(FenError::FieldCount(5)));
+    assert_eq!(parse("8/8/8/8/8/8/8 w - - 0 1", game_state), Err(FenError::RankCount(7)));
+    assert_eq!(parse("8/8/8/8/8/8/8/4K4 w - - 0 1", game_state), Err(FenError::RankLength { rank: 1, files: 9 }));
+    assert_eq!(
+        parse("8/8/8/8/8/8/8/4X3 w - - 0 1", game_state),
+        Err(FenError::InvalidCharacter { field: FenField::Position, character: 'X' }),
+    );
+    assert_eq!(
+        parse("8/8/8/8/8/8/8/8 w - - x 1", game_state),
+        Err(FenError::InvalidValue { field: FenField::HalfMoves, value: "x".to_string() }),
+    );
+}
+
+#[test]
+fn validates_semantics() {
+    let game_state = &mut GameState::new();
+    assert_eq!(parse_validated(POSITIONS[0], game_state), Ok(()));
+    assert_eq!(parse_validated("8/8/8/8/8/8/8/4K3 w - - 0 1", game_state), Err(FenError::KingCount { color: Color::Black, count: 0 }));
+    assert_eq!(parse_validated("P3k3/8/8/8/8/8/8/4K3 w - - 0 1", game_state), Err(FenError::PawnOnBackRank((0, 0))));
+    assert_eq!(parse_validated("4k3/8/8/8/8/8/8/4KR2 w - - 0 1", game_state), Ok(()));
+    assert_eq!(parse_validated("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1", game_state), Err(FenError::OpponentInCheck));
+    assert_eq!(parse_validated("4k3/8/8/8/8/8/8/4K3 b - e3 0 1", game_state), Err(FenError::InvalidEnPasant((5, 4))));
+}
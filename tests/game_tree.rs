@@ -0,0 +1,91 @@
+use rust_sfml_chess::engine::parser::{parse, INITIAL_FEN};
+use rust_sfml_chess::engine::pgn_reader::PgnReader;
+use rust_sfml_chess::engine::san::{parse_san, to_san};
+use rust_sfml_chess::engine::structs::game_state::GameState;
+use rust_sfml_chess::engine::structs::game_tree::GameTree;
+
+fn new_tree() -> GameTree {
+    let game_state = &mut GameState::new();
+    parse(INITIAL_FEN, game_state).unwrap();
+    GameTree::new(game_state.clone())
+}
+
+fn play(tree: &mut GameTree, san: &str) {
+    let chess_move = parse_san(tree.game_state(), san).unwrap();
+    tree.play_move(&chess_move).unwrap();
+}
+
+fn mainline_san(tree: &GameTree) -> Vec<String> {
+    let game_state = &mut tree.initial_state().clone();
+    tree.mainline().iter().map(|chess_move| {
+        let san = to_san(game_state, chess_move);
+        game_state.make_move(chess_move);
+        san
+    }).collect()
+}
+
+#[test]
+fn adds_variations_and_navigates() {
+    let tree = &mut new_tree();
+    play(tree, "e4");
+    play(tree, "e5");
+    tree.back().unwrap();
+    play(tree, "c5");
+    assert_eq!(tree.path(), [0, 1]);
+
+    tree.to_start();
+    assert!(tree.is_at_start());
+    tree.forward().unwrap();
+    tree.enter_variation(1).unwrap();
+    assert_eq!(tree.game_state().to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2");
+    assert!(tree.enter_variation(1).is_err());
+
+    // Replaying an existing move follows its node instead of adding a new one
+    tree.back().unwrap();
+    play(tree, "e5");
+    assert_eq!(tree.path(), [0, 0]);
+    assert_eq!(mainline_san(tree), ["e4", "e5"]);
+}
+
+#[test]
+fn promotes_demotes_and_deletes_variations() {
+    let tree = &mut new_tree();
+    play(tree, "e4");
+    play(tree, "e5");
+    tree.back().unwrap();
+    play(tree, "c5");
+    play(tree, "Nf3");
+
+    tree.promote_variation().unwrap();
+    assert_eq!(tree.path(), [0, 0, 0]);
+    assert_eq!(mainline_san(tree), ["e4", "c5", "Nf3"]);
+    assert!(tree.promote_variation().is_err());
+
+    tree.demote_variation().unwrap();
+    assert_eq!(mainline_san(tree), ["e4", "e5"]);
+
+    tree.delete_variation().unwrap();
+    assert_eq!(tree.path(), [0, 1]);
+    assert!(tree.current().children.is_empty());
+}
+
+#[test]
+fn builds_from_pgn() {
+    let pgn = "1. e4 e5 (1... c5 {Sicilian} 2. Nf3 (2. c3)) (1... e6) 2. Nf3 $1 *";
+    let game = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+    let tree = &mut GameTree::from_pgn(&game);
+
+    assert_eq!(mainline_san(tree), ["e4", "e5", "Nf3"]);
+    assert_eq!(tree.root().children[0].children.len(), 3);
+
+    tree.forward().unwrap();
+    tree.enter_variation(1).unwrap();
+    assert_eq!(tree.current().comments, ["Sicilian"]);
+    tree.forward().unwrap();
+    tree.back().unwrap();
+    assert_eq!(tree.current().children.len(), 2);
+    tree.to_start();
+    tree.to_end();
+    assert_eq!(tree.current().nags, [1]);
+    assert_eq!(tree.game_state().to_fen(), game.game_state.to_fen());
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::parser::validate_position;
+use rust_sfml_chess::engine::structs::enums::{GameResult, Variant, WinReason};
+
+const HORDE_FEN: &str = "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1";
+
+#[test]
+fn counts_horde_positions() {
+    let game_state = game(HORDE_FEN, Variant::Horde);
+    assert!(validate_position(&game_state).is_ok());
+    assert_eq!(game_state.perft(4), 23310);
+}
+
+#[test]
+fn pawns_double_push_from_first_rank() {
+    let game_state = &mut game("4k3/8/8/8/8/8/8/4P3 w - - 0 1", Variant::Horde);
+    play(game_state, "e3");
+    assert_eq!(game_state.to_fen(), "4k3/8/8/8/8/4P3/8/8 b - - 0 1");
+}
+
+#[test]
+fn capturing_every_white_piece_wins() {
+    let game_state = &mut game("4k3/8/8/8/8/8/3q4/4P3 b - - 0 1", Variant::Horde);
+    play(game_state, "Qxe1");
+    assert!(matches!(game_state.result, Some(GameResult::BlackWin(WinReason::AllPiecesCaptured))));
+}
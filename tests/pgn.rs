@@ -0,0 +1,133 @@
+use rust_sfml_chess::engine::pgn_reader::PgnReader;
+use rust_sfml_chess::engine::pgn_writer::write_pgn;
+use rust_sfml_chess::engine::structs::pgn_metadata::PgnMetadata;
+use rust_sfml_chess::engine::structs::pgn_error::PgnError;
+use rust_sfml_chess::engine::structs::pgn_game::PgnGame;
+use std::io::{self, BufReader, Read};
+
+const GAMES: &str = r#"[Event "Casual"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+{Open game} 1. e4 e5 2. Nf3!? $14 Nc6 (2... d6 {Philidor} 3. d4 (3. Bc4) exd4) 3. Bb5 ; Ruy Lopez
+a6 1-0
+
+[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"]
+
+1. e4 Kd7 2. e5 *
+
+1. e4 e5 2. Ke3 Nf6 1/2-1/2
+
+1. d4 d5 0-1
+"#;
+
+fn read_games(input: &str) -> Vec<Result<PgnGame, PgnError>> {
+    PgnReader::new(input.as_bytes()).collect()
+}
+
+#[test]
+fn reads_tags_and_movetext() {
+    let games = read_games(GAMES);
+    assert_eq!(games.len(), 4);
+
+    let game = games[0].as_ref().unwrap();
+    assert_eq!(game.tag("White"), Some("Alice"));
+    assert_eq!(game.result.as_deref(), Some("1-0"));
+    assert_eq!(game.moves.iter().map(|x| x.san.as_str()).collect::<Vec<&str>>(), ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6"]);
+    assert_eq!(game.moves[0].comments_before, ["Open game"]);
+    assert_eq!(game.moves[2].nags, [5, 14]);
+    assert_eq!(game.moves[4].comments, ["Ruy Lopez"]);
+    assert_eq!(game.game_state.to_fen(), "r1bqkbnr/1ppp1ppp/p1n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 4");
+}
+
+#[test]
+fn reads_nested_variations() {
+    let games = read_games(GAMES);
+    let variations = &games[0].as_ref().unwrap().moves[3].variations;
+    assert_eq!(variations.len(), 1);
+    assert_eq!(variations[0].iter().map(|x| x.san.as_str()).collect::<Vec<&str>>(), ["d6", "d4", "exd4"]);
+    assert_eq!(variations[0][0].comments, ["Philidor"]);
+    assert_eq!(variations[0][1].variations[0][0].san, "Bc4");
+}
+
+#[test]
+fn starts_from_fen_tag() {
+    let games = read_games(GAMES);
+    let game = games[1].as_ref().unwrap();
+    assert_eq!(game.initial_state.to_fen(), "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1");
+    assert_eq!(game.game_state.to_fen(), "8/3k4/8/4P3/8/8/8/4K3 b - - 0 2");
+}
+
+#[test]
+fn reports_illegal_move_and_continues() {
+    let games = read_games(GAMES);
+    let err = games[2].as_ref().unwrap_err();
+    assert_eq!((err.line, err.column), (14, 13));
+    assert_eq!(games[3].as_ref().unwrap().result.as_deref(), Some("0-1"));
+}
+
+#[test]
+fn ignores_comments_after_last_game() {
+    let games = read_games("1. e4 e5 1-0\n\n{Generated by a script}\n; end of file\n\n");
+    assert_eq!(games.len(), 1);
+
+    let games = read_games("{Opening} 1. d4 d5 *");
+    assert_eq!(games[0].as_ref().unwrap().moves[0].comments_before, ["Opening"]);
+}
+
+#[test]
+fn reports_invalid_utf8_position() {
+    let input = b"[White \"M\xfcller\"]\n\n1. e4 e5 1-0\n\n1. d4 d5 0-1\n";
+    let games: Vec<Result<PgnGame, PgnError>> = PgnReader::new(&input[..]).collect();
+    assert_eq!(games.len(), 2);
+
+    let err = games[0].as_ref().unwrap_err();
+    assert_eq!((err.line, err.column), (1, 10));
+    assert_eq!(games[1].as_ref().unwrap().result.as_deref(), Some("0-1"));
+}
+
+// Input whose every read fails, like reading a directory
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("read failed"))
+    }
+}
+
+#[test]
+fn stops_after_read_error() {
+    let mut reader = PgnReader::new(BufReader::new(FailingReader));
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.message, "read failed");
+    assert!(reader.next().is_none());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn writes_export_format() {
+    let games = read_games("1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0");
+    let game_state = &games[0].as_ref().unwrap().game_state;
+    let metadata = PgnMetadata { white: "Alice".to_string(), ..PgnMetadata::default() };
+
+    assert_eq!(
+        write_pgn(game_state, &metadata),
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"Alice\"]\n[Black \"?\"]\n\
+         [Result \"1-0\"]\n[Termination \"normal\"]\n\n1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# {White wins by checkmate} 1-0\n",
+    );
+}
+
+#[test]
+fn round_trips_written_games() {
+    let games = read_games(GAMES);
+    for game in games.iter().flatten() {
+        let output = write_pgn(&game.game_state, &PgnMetadata::default());
+        assert!(output.lines().all(|line| line.len() < 80));
+
+        let written = PgnReader::new(output.as_bytes()).next().unwrap().unwrap();
+        assert_eq!(written.initial_state.to_fen(), game.initial_state.to_fen());
+        assert_eq!(written.game_state.to_fen(), game.game_state.to_fen());
+    }
+}
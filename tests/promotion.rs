@@ -0,0 +1,44 @@
+mod common;
+
+use common::game;
+use rust_sfml_chess::engine::structs::enums::{Color, PieceType, Variant};
+use rust_sfml_chess::engine::structs::piece::Piece;
+
+const FEN: &str = "4k3/P7/8/8/8/8/4P3/4K1N1 w - - 0 1";
+
+#[test]
+fn requires_promotion_on_last_rank() {
+    let game_state = &mut game(FEN, Variant::Standard);
+    assert_eq!(game_state.execute_move((1, 0), (0, 0), None), Err("Promotion piece is required!"));
+    assert_eq!(game_state.to_fen(), FEN);
+}
+
+#[test]
+fn rejects_promotion_to_king() {
+    let game_state = &mut game(FEN, Variant::Standard);
+    let king = Some(Piece::new(PieceType::King, Color::White));
+    assert_eq!(game_state.execute_move((1, 0), (0, 0), king), Err("Pawn can only be promoted to a queen, rook, bishop or knight!"));
+    assert_eq!(game_state.to_fen(), FEN);
+}
+
+#[test]
+fn rejects_promotion_piece_on_other_moves() {
+    let game_state = &mut game(FEN, Variant::Standard);
+    let queen = Some(Piece::new(PieceType::Queen, Color::White));
+    let error = Err("Promotion is only allowed for pawns reaching the last rank!");
+
+    assert_eq!(game_state.execute_move((7, 6), (5, 5), queen), error);
+    assert_eq!(game_state.execute_move((6, 4), (4, 4), queen), error);
+    assert_eq!(game_state.to_fen(), FEN);
+}
+
+#[test]
+fn promotes_in_the_movers_color() {
+    let game_state = &mut game(FEN, Variant::Standard);
+    game_state.execute_move((1, 0), (0, 0), Some(Piece::new(PieceType::Knight, Color::Black))).unwrap();
+    assert_eq!(game_state.board.get(&(0, 0)), Some(Piece::new(PieceType::Knight, Color::White)));
+
+    let game_state = &mut game("4k3/8/8/8/8/8/p7/4K3 b - - 0 1", Variant::Standard);
+    game_state.execute_move((6, 0), (7, 0), Some(Piece::new(PieceType::Queen, Color::White))).unwrap();
+    assert_eq!(game_state.board.get(&(7, 0)), Some(Piece::new(PieceType::Queen, Color::Black)));
+}
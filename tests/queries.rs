@@ -0,0 +1,55 @@
+mod common;
+
+use common::game;
+use rust_sfml_chess::engine::structs::enums::{Color, Position, Variant};
+
+fn sorted(mut positions: Vec<Position>) -> Vec<Position> {
+    positions.sort();
+    positions
+}
+
+#[test]
+fn finds_double_check() {
+    let game_state = game("4k3/8/5N2/8/8/8/8/4R1K1 b - - 0 1", Variant::Standard);
+    assert!(game_state.is_in_check(Color::Black));
+    assert!(!game_state.is_in_check(Color::White));
+    assert_eq!(sorted(game_state.checkers()), [(2, 5), (7, 4)]);
+
+    let game_state = game("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1", Variant::Standard);
+    assert!(game_state.checkers().is_empty());
+}
+
+#[test]
+fn finds_pin_ray_up_to_pinner() {
+    let game_state = game("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1", Variant::Standard);
+    let pins = game_state.pinned_pieces(Color::White);
+    assert_eq!(pins.len(), 1);
+    assert_eq!((pins[0].pinned, pins[0].pinner), ((6, 4), (1, 4)));
+    assert_eq!(sorted(pins[0].ray.clone()), [(1, 4), (2, 4), (3, 4), (4, 4), (5, 4), (6, 4)]);
+
+    // Two pieces between the king and the rook are not pinned
+    let game_state = game("4k3/4r3/8/8/8/4N3/4B3/4K3 w - - 0 1", Variant::Standard);
+    assert!(game_state.pinned_pieces(Color::White).is_empty());
+    assert!(game_state.pinned_pieces(Color::Black).is_empty());
+}
+
+#[test]
+fn ignores_x_ray_attacks() {
+    let game_state = game("4k3/8/8/8/8/8/R7/R3K3 w - - 0 1", Variant::Standard);
+    assert_eq!(game_state.attackers_of((0, 0), Color::White), [(6, 0)]);
+
+    // Queen behind a bishop only sees the bishop's square
+    let game_state = game("4k3/8/8/8/8/8/1B6/Q3K3 w - - 0 1", Variant::Standard);
+    assert_eq!(game_state.attackers_of((4, 3), Color::White), [(6, 1)]);
+    assert!(game_state.is_square_attacked((6, 1), Color::White));
+}
+
+#[test]
+fn finds_attackers_of_empty_squares() {
+    let game_state = game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Variant::Standard);
+    assert_eq!(sorted(game_state.attackers_of((5, 4), Color::White)), [(6, 3), (6, 5)]);
+    assert_eq!(sorted(game_state.attackers_of((5, 5), Color::White)), [(6, 4), (6, 6), (7, 6)]);
+    assert!(game_state.attackers_of((4, 4), Color::White).is_empty());
+    assert!(!game_state.is_square_attacked((4, 4), Color::White));
+    assert!(game_state.is_square_attacked((2, 5), Color::Black));
+}
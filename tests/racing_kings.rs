@@ -0,0 +1,35 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::san::parse_san;
+use rust_sfml_chess::engine::structs::enums::{DrawReason, GameResult, Variant, WinReason};
+
+#[test]
+fn counts_racing_kings_positions() {
+    assert_eq!(game("8/8/8/8/8/8/krbnNBRK/qrbnNBRQ w - - 0 1", Variant::RacingKings).perft(3), 11264);
+}
+
+#[test]
+fn moves_giving_check_are_illegal() {
+    let game_state = game("8/8/8/8/k7/8/8/1R5K w - - 0 1", Variant::RacingKings);
+    assert!(parse_san(&game_state, "Ra1").is_err());
+    assert!(parse_san(&game_state, "Rb2").is_ok());
+}
+
+#[test]
+fn black_may_equalise_after_white_reaches_goal() {
+    let game_state = &mut game("8/1k4K1/8/8/8/8/8/8 w - - 0 1", Variant::RacingKings);
+    play(game_state, "Kg8");
+    assert!(game_state.result.is_none());
+    play(game_state, "Kb8");
+    assert!(matches!(game_state.result, Some(GameResult::Draw(DrawReason::BothKingsReachedGoal))));
+
+    game_state.undo_move().unwrap();
+    play(game_state, "Ka6");
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::KingReachedGoal))));
+
+    // Black king too far from the goal to equalise
+    let game_state = &mut game("8/6K1/1k6/8/8/8/8/8 w - - 0 1", Variant::RacingKings);
+    play(game_state, "Kg8");
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::KingReachedGoal))));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::game;
+use rust_sfml_chess::engine::san::{parse_san, to_san};
+use rust_sfml_chess::engine::structs::enums::Variant;
+use rust_sfml_chess::engine::structs::san_error::SanError;
+use rust_sfml_chess::engine::uci::{parse_uci_move, to_uci};
+
+fn parse_to_uci(fen: &str, san: &str) -> Result<String, SanError> {
+    parse_san(&game(fen, Variant::Standard), san).map(|x| to_uci(&x))
+}
+
+fn uci_to_san(fen: &str, uci: &str) -> String {
+    let game_state = game(fen, Variant::Standard);
+    to_san(&game_state, &parse_uci_move(&game_state, uci).unwrap())
+}
+
+#[test]
+fn reports_ambiguous_moves_with_candidates() {
+    let fen = "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1";
+    match parse_to_uci(fen, "Nd2") {
+        Err(SanError::Ambiguous { san, candidates }) => {
+            assert_eq!(san, "Nd2");
+            let mut candidates: Vec<String> = candidates.iter().map(to_uci).collect();
+            candidates.sort();
+            assert_eq!(candidates, ["b1d2", "f1d2"]);
+        },
+        other => panic!("expected an ambiguity error, got {:?}", other),
+    }
+    assert_eq!(parse_to_uci(fen, "Nbd2").unwrap(), "b1d2");
+}
+
+#[test]
+fn reports_illegal_moves() {
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_eq!(parse_to_uci(fen, "Ke2"), Err(SanError::Illegal("Ke2".to_string())));
+    assert_eq!(parse_to_uci(fen, "e5"), Err(SanError::Illegal("e5".to_string())));
+    assert_eq!(parse_to_uci(fen, "Nf9"), Err(SanError::Invalid("Nf9".to_string())));
+}
+
+#[test]
+fn disambiguates_by_rank_and_file() {
+    let fen = "7k/8/8/8/8/4R3/8/K3R3 w - - 0 1";
+    assert_eq!(parse_to_uci(fen, "R1e2").unwrap(), "e1e2");
+    assert_eq!(parse_to_uci(fen, "R3e2").unwrap(), "e3e2");
+    assert!(matches!(parse_to_uci(fen, "Re2"), Err(SanError::Ambiguous { .. })));
+
+    let fen = "1n2k3/8/5n2/8/8/8/8/4K3 b - - 0 1";
+    assert_eq!(parse_to_uci(fen, "Nbd7").unwrap(), "b8d7");
+    assert_eq!(parse_to_uci(fen, "Nfd7").unwrap(), "f6d7");
+}
+
+#[test]
+fn parses_castling() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+    assert_eq!(parse_to_uci(fen, "O-O").unwrap(), "e1g1");
+    assert_eq!(parse_to_uci(fen, "O-O-O").unwrap(), "e1c1");
+    assert_eq!(parse_to_uci(fen, "0-0").unwrap(), "e1g1");
+}
+
+#[test]
+fn parses_promotions() {
+    let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+    assert_eq!(parse_to_uci(fen, "a8=Q+").unwrap(), "a7a8q");
+    assert_eq!(parse_to_uci(fen, "a8N").unwrap(), "a7a8n");
+    assert_eq!(parse_to_uci(fen, "a8"), Err(SanError::MissingPromotion("a8".to_string())));
+
+    let fen = "k7/4P3/8/8/8/8/8/4K3 w - - 0 1";
+    assert_eq!(parse_to_uci(fen, "e8=Q+").unwrap(), "e7e8q");
+}
+
+#[test]
+fn writes_minimal_disambiguation() {
+    let fen = "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1";
+    assert_eq!(uci_to_san(fen, "b1d2"), "Nbd2");
+    assert_eq!(uci_to_san(fen, "b1c3"), "Nc3");
+
+    let fen = "7k/8/8/8/8/4R3/8/K3R3 w - - 0 1";
+    assert_eq!(uci_to_san(fen, "e1e2"), "R1e2");
+    assert_eq!(uci_to_san(fen, "e3e2"), "R3e2");
+
+    let fen = "4k3/8/8/8/8/Q7/8/Q1Q4K w - - 0 1";
+    assert_eq!(uci_to_san(fen, "a1b2"), "Qa1b2");
+    assert_eq!(uci_to_san(fen, "c1b2"), "Qcb2");
+    assert_eq!(uci_to_san(fen, "a3b2"), "Q3b2");
+}
+
+#[test]
+fn writes_check_and_mate_suffixes() {
+    assert_eq!(uci_to_san("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8q"), "e8=Q+");
+    assert_eq!(uci_to_san("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1", "a1a8"), "Ra8#");
+    assert_eq!(uci_to_san("k7/4P3/1K6/8/8/8/8/8 w - - 0 1", "e7e8q"), "e8=Q#");
+    assert_eq!(uci_to_san("k7/4P3/1K6/8/8/8/8/8 w - - 0 1", "e7e8n"), "e8=N");
+}
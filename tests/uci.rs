@@ -0,0 +1,51 @@
+mod common;
+
+use common::game;
+use rust_sfml_chess::engine::parser::parse_move;
+use rust_sfml_chess::engine::structs::enums::{Color, PieceType, Variant};
+use rust_sfml_chess::engine::structs::piece::Piece;
+use rust_sfml_chess::engine::uci::{parse_uci, parse_uci_move, to_uci};
+
+#[test]
+fn round_trips_moves() {
+    let cases = [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "e2e4"),
+        ("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8q"),
+        ("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8n"),
+        ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1g1"),
+        ("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "e1c1"),
+    ];
+
+    for (fen, uci) in cases {
+        let chess_move = parse_uci_move(&game(fen, Variant::Standard), uci).unwrap();
+        assert_eq!(to_uci(&chess_move), uci);
+    }
+
+    let castle = parse_uci_move(&game("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", Variant::Standard), "e1g1").unwrap();
+    assert!(castle.is_castle());
+}
+
+#[test]
+fn rejects_bad_promotion_letters() {
+    assert_eq!(parse_uci("e7e8x"), Err("Invalid promotion piece!"));
+    assert_eq!(parse_uci("e7e8Q"), Err("Invalid promotion piece!"));
+    assert_eq!(parse_uci("e7e8p"), Err("Invalid promotion piece!"));
+}
+
+#[test]
+fn rejects_bad_lengths() {
+    for input in ["", "e2", "e2e", "e2e4qq", "e2-e4"] {
+        assert!(parse_uci(input).is_err(), "{} was accepted", input);
+    }
+    assert!(parse_uci("i2e4").is_err());
+    assert!(parse_uci("e0e4").is_err());
+}
+
+#[test]
+fn parses_colon_syntax() {
+    let promotion = Some(Piece::new(PieceType::Queen, Color::Black));
+    assert_eq!(parse_move("e7:e8:q".to_string()), Ok(((1, 4), (0, 4), promotion)));
+    assert_eq!(parse_move("e2:e4".to_string()), Ok(((6, 4), (4, 4), None)));
+    assert!(parse_move("e7:e8:x".to_string()).is_err());
+    assert!(parse_move("e7e8".to_string()).is_err());
+}
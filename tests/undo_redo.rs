@@ -0,0 +1,74 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::structs::enums::{GameResult, Variant, WinReason};
+use rust_sfml_chess::engine::structs::game_state::GameState;
+
+// FEN and result, GameResult can't be compared directly
+fn snapshot(game_state: &GameState) -> (String, String) {
+    (game_state.to_fen(), format!("{:?}", game_state.result))
+}
+
+// Plays the move, then checks that undoing and redoing it restore both sides exactly
+fn assert_undo_redo(fen: &str, san: &str) -> GameState {
+    let game_state = &mut game(fen, Variant::Standard);
+    let before = snapshot(game_state);
+    play(game_state, san);
+    let after = snapshot(game_state);
+    assert_ne!(before, after);
+
+    game_state.undo_move().unwrap();
+    assert_eq!(snapshot(game_state), before);
+    assert_eq!(game_state.redo_stack.len(), 1);
+
+    game_state.redo_move().unwrap();
+    assert_eq!(snapshot(game_state), after);
+    assert!(game_state.redo_stack.is_empty());
+
+    game_state.clone()
+}
+
+#[test]
+fn undoes_castling() {
+    let game_state = assert_undo_redo("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", "O-O");
+    assert_eq!(game_state.to_fen(), "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1");
+    assert_undo_redo("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "O-O-O");
+}
+
+#[test]
+fn undoes_en_pasant() {
+    let game_state = assert_undo_redo("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2", "exd6");
+    assert_eq!(game_state.to_fen(), "4k3/8/3P4/8/8/8/8/4K3 b - - 0 2");
+}
+
+#[test]
+fn undoes_promotion() {
+    let game_state = assert_undo_redo("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a8=Q+");
+    assert_eq!(game_state.to_fen(), "Q3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+}
+
+#[test]
+fn undoes_game_ending_move() {
+    let game_state = assert_undo_redo("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq g3 0 2", "Qh4#");
+    assert!(matches!(game_state.result, Some(GameResult::BlackWin(WinReason::Checkmate))));
+
+    let game_state = &mut game_state.clone();
+    game_state.undo_move().unwrap();
+    assert!(game_state.result.is_none());
+    play(game_state, "Nc6");
+}
+
+#[test]
+fn new_move_clears_redo_stack() {
+    let game_state = &mut game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Variant::Standard);
+    play(game_state, "e4");
+    play(game_state, "e5");
+    game_state.undo_move().unwrap();
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.redo_stack.len(), 2);
+
+    play(game_state, "d4");
+    assert!(game_state.redo_stack.is_empty());
+    assert_eq!(game_state.redo_move(), Err("No moves to redo!"));
+    assert_eq!(game_state.to_fen(), "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 1");
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{game, play};
+use rust_sfml_chess::engine::parser::parse;
+use rust_sfml_chess::engine::structs::enums::{Color, GameResult, Variant, WinReason};
+use rust_sfml_chess::engine::structs::game_state::GameState;
+
+#[test]
+fn reads_and_writes_check_counters() {
+    let game_state = &mut GameState::new();
+    parse("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2 +1+0", game_state).unwrap();
+    assert_eq!(game_state.variant, Variant::ThreeCheck);
+    assert_eq!(game_state.checks[Color::White as usize], 1);
+
+    play(game_state, "Bb5");
+    assert_eq!(game_state.to_fen(), "rnbqkbnr/pppp1ppp/8/1B2p3/4P3/8/PPPP1PPP/RNBQK1NR b KQkq - 1 2 +1+0");
+
+    assert!(parse("8/8/8/8/8/8/8/k6K w - - 0 1 +4+0", game_state).is_err());
+    assert!(parse("8/8/8/8/8/8/8/k6K w - - 0 1 1+0", game_state).is_err());
+}
+
+#[test]
+fn third_check_wins() {
+    let game_state = &mut game("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", Variant::ThreeCheck);
+    for san in ["e4", "d5", "Bb5+"] {
+        play(game_state, san);
+    }
+    assert_eq!(game_state.checks[Color::White as usize], 1);
+
+    let game_state = &mut game("rnbqkbnr/ppp2ppp/8/3pp3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3 +2+0", Variant::ThreeCheck);
+    play(game_state, "Bb5+");
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::ThirdCheck))));
+
+    game_state.undo_move().unwrap();
+    assert_eq!(game_state.checks[Color::White as usize], 2);
+    assert!(game_state.result.is_none());
+}
+
+#[test]
+fn king_reaching_the_hill_wins() {
+    let game_state = &mut game("7R/8/8/8/8/2K5/8/k7 w - - 0 1", Variant::KingOfTheHill);
+    play(game_state, "Kd4");
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::KingOfTheHill))));
+    assert_eq!(game_state.result.as_ref().unwrap().to_string(), "White wins by reaching the hill");
+
+    let game_state = &mut game("7R/8/8/8/8/2K5/8/k7 w - - 0 1", Variant::Standard);
+    play(game_state, "Kd4");
+    assert!(game_state.result.is_none());
+}
+
+#[test]
+fn bare_king_can_still_reach_the_hill() {
+    let game_state = &mut game("8/8/8/8/8/2K5/8/k7 w - - 0 1", Variant::KingOfTheHill);
+    play(game_state, "Kc4");
+    assert!(game_state.result.is_none());
+    play(game_state, "Kb2");
+    play(game_state, "Kd4");
+    assert!(matches!(game_state.result, Some(GameResult::WhiteWin(WinReason::KingOfTheHill))));
+}
+
+#[test]
+fn minor_piece_can_still_give_checks() {
+    let game_state = &mut game("4k3/8/8/8/8/8/8/4KN2 w - - 0 1 +0+0", Variant::ThreeCheck);
+    play(game_state, "Nd2");
+    assert!(game_state.result.is_none());
+    assert!(!game_state.is_insufficient_material());
+
+    assert!(game("4k3/8/8/8/8/8/8/4K3 w - - 0 1 +2+0", Variant::ThreeCheck).is_insufficient_material());
+}